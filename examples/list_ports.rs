@@ -15,6 +15,20 @@ fn main() {
 
             for p in ports {
                 println!("    {}", p.port_name);
+                println!("        Description: {}", p.description());
+                #[cfg(feature = "serialportinfo-details")]
+                for alias in &p.aliases {
+                    println!("        Alias: {}", alias);
+                }
+                #[cfg(feature = "serialportinfo-details")]
+                {
+                    if let Some(ref driver) = p.driver {
+                        println!("        Driver: {}", driver);
+                    }
+                    if let Some(ref sysfs_path) = p.sysfs_path {
+                        println!("        Sysfs Path: {}", sysfs_path);
+                    }
+                }
                 match p.port_type {
                     SerialPortType::UsbPort(info) => {
                         println!("        Type: USB");
@@ -39,6 +53,26 @@ fn main() {
                             "        Product: {}",
                             info.product.as_ref().map_or("", String::as_str)
                         );
+                        #[cfg(feature = "usbportinfo-details")]
+                        {
+                            if let Some(ref interface) = info.interface_description {
+                                println!("        Interface Description: {}", interface);
+                            }
+                            if let Some(bcd_device) = info.bcd_device {
+                                println!("        bcdDevice: {:04x}", bcd_device);
+                            }
+                            if let (Some(bus), Some(address)) =
+                                (info.bus_number, info.device_address)
+                            {
+                                println!("        Bus {:03} Device {:03}", bus, address);
+                            }
+                            if let Some(ref port_path) = info.port_path {
+                                println!("        Port Path: {}", port_path);
+                            }
+                        }
+                        if let Some(chip) = info.chip() {
+                            println!("        Chip: {}", chip.name);
+                        }
                     }
                     SerialPortType::BluetoothPort => {
                         println!("        Type: Bluetooth");
@@ -46,6 +80,19 @@ fn main() {
                     SerialPortType::PciPort => {
                         println!("        Type: PCI");
                     }
+                    #[cfg(feature = "serialporttype-details")]
+                    SerialPortType::PlatformPort { driver } => {
+                        println!("        Type: Platform");
+                        println!("        Driver: {}", driver.as_deref().unwrap_or(""));
+                    }
+                    #[cfg(feature = "serialporttype-details")]
+                    SerialPortType::PseudoTerminal => {
+                        println!("        Type: Pseudo terminal");
+                    }
+                    #[cfg(feature = "serialporttype-details")]
+                    SerialPortType::UsbGadget => {
+                        println!("        Type: USB gadget");
+                    }
                     SerialPortType::Unknown => {
                         println!("        Type: Unknown");
                     }
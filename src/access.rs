@@ -0,0 +1,294 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Details on why a device could not be accessed as returned by `Error::access_diagnostics`
+///
+/// These are collected when opening a port fails because of missing permissions or because the
+/// port is a symbolic link to a device node which does not exist (like a stale udev link). The
+/// description of the error contains the same information in prose. `Error::access_diagnostics`
+/// is available with the feature `error-details`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessDiagnostics {
+    /// Path of the device as given for opening it
+    pub path: PathBuf,
+    /// Target of the symbolic link if `path` is one
+    pub symlink_target: Option<PathBuf>,
+    /// Whether `path` is a symbolic link to a node which does not exist
+    pub dangling_symlink: bool,
+    /// User ID of the owner of the device
+    pub uid: Option<u32>,
+    /// Name of the owner of the device
+    pub user: Option<String>,
+    /// Group ID of the device
+    pub gid: Option<u32>,
+    /// Name of the group of the device (like `dialout` or `uucp`)
+    pub group: Option<String>,
+    /// Permission bits of the device (like `0o660`)
+    pub mode: Option<u32>,
+    /// Whether the current process is a member of the group of the device
+    pub in_group: Option<bool>,
+    /// Whether the group database lists the current user as a member of the group of the device
+    ///
+    /// A user which is listed but not `in_group` has been added to the group and needs to log in
+    /// again for this to take effect.
+    pub listed_in_group: Option<bool>,
+}
+
+impl fmt::Display for AccessDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.display();
+        if self.dangling_symlink {
+            return match self.symlink_target {
+                Some(ref target) => write!(
+                    f,
+                    "{} is a symbolic link to {}, which does not exist",
+                    path,
+                    target.display()
+                ),
+                None => write!(f, "{} is a symbolic link to a missing device", path),
+            };
+        }
+
+        let name = |name: &Option<String>, id: Option<u32>| match (name, id) {
+            (Some(name), _) => name.clone(),
+            (None, Some(id)) => id.to_string(),
+            (None, None) => "?".to_string(),
+        };
+        let group = name(&self.group, self.gid);
+        write!(
+            f,
+            "{} is owned by {}:{}",
+            path,
+            name(&self.user, self.uid),
+            group
+        )?;
+        if let Some(mode) = self.mode {
+            write!(f, " with mode {:04o}", mode)?;
+        }
+        match (self.in_group, self.listed_in_group) {
+            (Some(false), Some(true)) => write!(
+                f,
+                "; the current user has been added to group {} but needs to log in again",
+                group
+            ),
+            (Some(false), _) => write!(f, "; the current user is not a member of group {}", group),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use self::unix::diagnose;
+
+#[cfg(unix)]
+mod unix {
+    use std::ffi::CStr;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+    use std::{fs, mem, ptr};
+
+    use nix::libc;
+
+    use super::AccessDiagnostics;
+
+    /// Size of the buffer for the strings of user and group entries
+    const BUFFER_SIZE: usize = 16 * 1024;
+
+    /// Collects the ownership of the device at `path` and the group memberships of the current
+    /// user.
+    pub(crate) fn diagnose(path: &Path) -> AccessDiagnostics {
+        let symlink_target = fs::symlink_metadata(path)
+            .ok()
+            .filter(|metadata| metadata.file_type().is_symlink())
+            .and_then(|_| fs::read_link(path).ok());
+        let metadata = fs::metadata(path).ok();
+
+        let mut diagnostics = AccessDiagnostics {
+            path: path.to_path_buf(),
+            dangling_symlink: symlink_target.is_some() && metadata.is_none(),
+            symlink_target,
+            uid: None,
+            user: None,
+            gid: None,
+            group: None,
+            mode: None,
+            in_group: None,
+            listed_in_group: None,
+        };
+        if let Some(metadata) = metadata {
+            let gid = metadata.gid();
+            let members = group_members(gid);
+            diagnostics.uid = Some(metadata.uid());
+            diagnostics.user = user_name(metadata.uid());
+            diagnostics.gid = Some(gid);
+            diagnostics.group = members.as_ref().map(|(name, _)| name.clone());
+            diagnostics.mode = Some(metadata.mode() & 0o7777);
+            diagnostics.in_group = process_groups().map(|groups| groups.contains(&gid));
+            let current_user = user_name(unsafe { libc::geteuid() });
+            diagnostics.listed_in_group = match (members, current_user) {
+                (Some((_, members)), Some(user)) => Some(members.contains(&user)),
+                _ => None,
+            };
+        }
+        diagnostics
+    }
+
+    /// Looks up the name of the user with ID `uid`.
+    fn user_name(uid: u32) -> Option<String> {
+        let mut buffer = vec![0 as libc::c_char; BUFFER_SIZE];
+        let mut passwd: libc::passwd = unsafe { mem::zeroed() };
+        let mut result = ptr::null_mut();
+        let rc = unsafe {
+            libc::getpwuid_r(
+                uid,
+                &mut passwd,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+        if rc != 0 || result.is_null() {
+            return None;
+        }
+        Some(string(passwd.pw_name))
+    }
+
+    /// Looks up the name and the members of the group with ID `gid`.
+    fn group_members(gid: u32) -> Option<(String, Vec<String>)> {
+        let mut buffer = vec![0 as libc::c_char; BUFFER_SIZE];
+        let mut group: libc::group = unsafe { mem::zeroed() };
+        let mut result = ptr::null_mut();
+        let rc = unsafe {
+            libc::getgrgid_r(
+                gid,
+                &mut group,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+        if rc != 0 || result.is_null() {
+            return None;
+        }
+
+        let mut members = Vec::new();
+        let mut member = group.gr_mem;
+        while !member.is_null() && !unsafe { *member }.is_null() {
+            members.push(string(unsafe { *member }));
+            member = unsafe { member.add(1) };
+        }
+        Some((string(group.gr_name), members))
+    }
+
+    /// Returns the effective and supplementary group IDs of the current process.
+    fn process_groups() -> Option<Vec<u32>> {
+        let count = unsafe { libc::getgroups(0, ptr::null_mut()) };
+        if count < 0 {
+            return None;
+        }
+        let mut groups = vec![0 as libc::gid_t; count as usize];
+        let count = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+        if count < 0 {
+            return None;
+        }
+        groups.truncate(count as usize);
+        groups.push(unsafe { libc::getegid() });
+        Some(groups)
+    }
+
+    fn string(s: *const libc::c_char) -> String {
+        unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::tests::fs::TempDir;
+        use std::os::unix::fs::PermissionsExt;
+
+        #[test]
+        fn diagnose_reports_owner_and_mode() {
+            let dir = TempDir::new("access");
+            let device = dir.file("ttyUSB0", "");
+            fs::set_permissions(&device, fs::Permissions::from_mode(0o660)).unwrap();
+
+            let diagnostics = diagnose(&device);
+            assert!(!diagnostics.dangling_symlink);
+            assert_eq!(diagnostics.symlink_target, None);
+            assert_eq!(diagnostics.uid, Some(unsafe { libc::geteuid() }));
+            assert_eq!(diagnostics.mode, Some(0o660));
+            // Files are created with the effective group of the process (or of the directory).
+            assert_eq!(diagnostics.gid, Some(fs::metadata(&device).unwrap().gid()));
+        }
+
+        #[test]
+        fn diagnose_detects_dangling_symlinks() {
+            let dir = TempDir::new("access");
+            let target = dir.path().join("ttyUSB7");
+            let link = dir.symlink("serial/by-id/usb-FTDI-if00", &target);
+
+            let diagnostics = diagnose(&link);
+            assert!(diagnostics.dangling_symlink);
+            assert_eq!(diagnostics.symlink_target, Some(target.clone()));
+            assert_eq!(diagnostics.uid, None);
+            assert_eq!(
+                diagnostics.to_string(),
+                format!(
+                    "{} is a symbolic link to {}, which does not exist",
+                    link.display(),
+                    target.display()
+                )
+            );
+
+            let error = crate::new(link.to_str().unwrap(), 9600).open().unwrap_err();
+            #[cfg(feature = "error-details")]
+            assert_eq!(error.access_diagnostics(), Some(&diagnostics));
+            assert!(error.description.ends_with("which does not exist"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostics() -> AccessDiagnostics {
+        AccessDiagnostics {
+            path: PathBuf::from("/dev/ttyUSB0"),
+            symlink_target: None,
+            dangling_symlink: false,
+            uid: Some(0),
+            user: Some("root".to_string()),
+            gid: Some(20),
+            group: Some("dialout".to_string()),
+            mode: Some(0o660),
+            in_group: Some(true),
+            listed_in_group: Some(true),
+        }
+    }
+
+    #[test]
+    fn access_diagnostics_display() {
+        let mut diagnostics = diagnostics();
+        assert_eq!(
+            diagnostics.to_string(),
+            "/dev/ttyUSB0 is owned by root:dialout with mode 0660"
+        );
+
+        diagnostics.in_group = Some(false);
+        assert_eq!(
+            diagnostics.to_string(),
+            "/dev/ttyUSB0 is owned by root:dialout with mode 0660; the current user has been \
+             added to group dialout but needs to log in again"
+        );
+
+        diagnostics.listed_in_group = Some(false);
+        diagnostics.group = None;
+        assert_eq!(
+            diagnostics.to_string(),
+            "/dev/ttyUSB0 is owned by root:20 with mode 0660; the current user is not a member \
+             of group 20"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    DataBits, Error, ErrorKind, FlowControl, Result, SerialPortBuilder, SerialPortType, StopBits,
+    UsbPortInfo,
+};
+
+/// Capabilities of a USB serial adapter chip as returned by [`UsbPortInfo::chip`]
+///
+/// Drivers often silently ignore settings their hardware does not support. The capabilities allow
+/// to detect this upfront with [`ChipInfo::check`] or
+/// [`SerialPortBuilder::check_usb_chip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChipInfo {
+    /// Name of the chip (like `FT232R` or `CP2102`)
+    pub name: &'static str,
+    /// Maximum baud rate in symbols-per-second or `None` if the chip does not impose a limit
+    /// (like CDC-ACM devices without an actual UART)
+    pub max_baud_rate: Option<u32>,
+    /// Supported numbers of bits per character
+    pub data_bits: &'static [DataBits],
+    /// Supported numbers of stop bits
+    pub stop_bits: &'static [StopBits],
+    /// Whether hardware flow control (RTS/CTS) actually works
+    pub hardware_flow_control: bool,
+}
+
+impl ChipInfo {
+    /// Checks whether the chip supports the settings of `builder`
+    ///
+    /// ## Errors
+    ///
+    /// `InvalidInput` describing the first unsupported setting.
+    pub fn check(&self, builder: &SerialPortBuilder) -> Result<()> {
+        let unsupported = |setting: String| {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{} does not support {}", self.name, setting),
+            ))
+        };
+
+        if let Some(max_baud_rate) = self.max_baud_rate {
+            if builder.baud_rate > max_baud_rate {
+                return unsupported(format!(
+                    "{} baud (maximum is {} baud)",
+                    builder.baud_rate, max_baud_rate
+                ));
+            }
+        }
+        if !self.data_bits.contains(&builder.data_bits) {
+            return unsupported(format!("{} data bits", u8::from(builder.data_bits)));
+        }
+        if !self.stop_bits.contains(&builder.stop_bits) {
+            return unsupported(format!("{} stop bits", u8::from(builder.stop_bits)));
+        }
+        if builder.flow_control == FlowControl::Hardware && !self.hardware_flow_control {
+            return unsupported("hardware flow control".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl SerialPortBuilder {
+    /// Checks the settings against the USB adapter chip of the port if requested with
+    /// [`SerialPortBuilder::check_usb_chip`].
+    pub(crate) fn verify_usb_chip(&self) -> Result<()> {
+        if !self.check_usb_chip {
+            return Ok(());
+        }
+        // Failing to look up the port is left to opening it.
+        match crate::port_info(&self.path).map(|info| info.port_type) {
+            Ok(SerialPortType::UsbPort(info)) => match info.chip() {
+                Some(chip) => chip.check(self),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl UsbPortInfo {
+    /// Looks up the capabilities of the adapter chip by its vendor and product ID
+    ///
+    /// This uses a built-in table of common USB serial adapters and returns `None` for unknown
+    /// devices. Chips sharing the same IDs are told apart by their device release where possible,
+    /// which requires the feature `usbportinfo-details`.
+    ///
+    /// ```
+    /// use serialport::UsbPortInfo;
+    ///
+    /// let info = UsbPortInfo {
+    ///     vid: 0x10c4,
+    ///     pid: 0xea60,
+    ///     ..Default::default()
+    /// };
+    /// let chip = info.chip().unwrap();
+    /// assert_eq!(chip.name, "CP2102");
+    /// assert_eq!(chip.max_baud_rate, Some(921_600));
+    /// ```
+    pub fn chip(&self) -> Option<&'static ChipInfo> {
+        CHIPS
+            .iter()
+            .find(|chip| {
+                chip.vid == self.vid
+                    && chip.pid == self.pid
+                    && (chip.bcd_device.is_none() || chip.bcd_device == self.device_release())
+            })
+            .map(|chip| &chip.info)
+    }
+
+    #[cfg(feature = "usbportinfo-details")]
+    fn device_release(&self) -> Option<u16> {
+        self.bcd_device
+    }
+
+    #[cfg(not(feature = "usbportinfo-details"))]
+    fn device_release(&self) -> Option<u16> {
+        None
+    }
+}
+
+struct Chip {
+    vid: u16,
+    pid: u16,
+    /// Restricts the entry to a particular device release. Entries with a device release need to
+    /// come before the generic ones for the same IDs.
+    bcd_device: Option<u16>,
+    info: ChipInfo,
+}
+
+const FIVE_TO_EIGHT: &[DataBits] = &[
+    DataBits::Five,
+    DataBits::Six,
+    DataBits::Seven,
+    DataBits::Eight,
+];
+const SEVEN_TO_EIGHT: &[DataBits] = &[DataBits::Seven, DataBits::Eight];
+const ONE_OR_TWO: &[StopBits] = &[StopBits::One, StopBits::Two];
+
+const fn chip(
+    vid: u16,
+    pid: u16,
+    bcd_device: Option<u16>,
+    name: &'static str,
+    max_baud_rate: Option<u32>,
+    data_bits: &'static [DataBits],
+    hardware_flow_control: bool,
+) -> Chip {
+    Chip {
+        vid,
+        pid,
+        bcd_device,
+        info: ChipInfo {
+            name,
+            max_baud_rate,
+            data_bits,
+            stop_bits: ONE_OR_TWO,
+            hardware_flow_control,
+        },
+    }
+}
+
+#[rustfmt::skip]
+static CHIPS: &[Chip] = &[
+    // FTDI
+    chip(0x0403, 0x6001, Some(0x0200), "FT232AM", Some(3_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6001, Some(0x0400), "FT232BM", Some(3_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6001, None, "FT232R", Some(3_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6010, Some(0x0500), "FT2232C", Some(3_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6010, None, "FT2232H", Some(12_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6011, None, "FT4232H", Some(12_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6014, None, "FT232H", Some(12_000_000), SEVEN_TO_EIGHT, true),
+    chip(0x0403, 0x6015, None, "FT-X", Some(3_000_000), SEVEN_TO_EIGHT, true),
+    // Silicon Labs
+    chip(0x10c4, 0xea60, None, "CP2102", Some(921_600), FIVE_TO_EIGHT, true),
+    chip(0x10c4, 0xea70, None, "CP2105", Some(2_000_000), FIVE_TO_EIGHT, true),
+    chip(0x10c4, 0xea71, None, "CP2108", Some(2_000_000), FIVE_TO_EIGHT, true),
+    // WCH (the Linux driver for the CH340/CH341 does not support RTS/CTS)
+    chip(0x1a86, 0x7523, None, "CH340", Some(2_000_000), FIVE_TO_EIGHT, false),
+    chip(0x1a86, 0x5523, None, "CH341", Some(2_000_000), FIVE_TO_EIGHT, false),
+    chip(0x1a86, 0x55d4, None, "CH9102", Some(4_000_000), FIVE_TO_EIGHT, true),
+    // Prolific
+    chip(0x067b, 0x2303, Some(0x0300), "PL2303HX", Some(6_000_000), FIVE_TO_EIGHT, true),
+    chip(0x067b, 0x2303, Some(0x0400), "PL2303HXD", Some(12_000_000), FIVE_TO_EIGHT, true),
+    chip(0x067b, 0x2303, None, "PL2303", Some(1_228_800), FIVE_TO_EIGHT, true),
+    chip(0x067b, 0x23a3, None, "PL2303GC", Some(12_000_000), FIVE_TO_EIGHT, true),
+    chip(0x067b, 0x23c3, None, "PL2303GT", Some(12_000_000), FIVE_TO_EIGHT, true),
+    chip(0x067b, 0x23d3, None, "PL2303GL", Some(12_000_000), FIVE_TO_EIGHT, true),
+    // CDC-ACM devices implementing the serial port in firmware
+    chip(0x2341, 0x0043, None, "CDC-ACM (Arduino Uno)", None, FIVE_TO_EIGHT, false),
+    chip(0x2341, 0x0042, None, "CDC-ACM (Arduino Mega 2560)", None, FIVE_TO_EIGHT, false),
+    chip(0x2e8a, 0x000a, None, "CDC-ACM (Raspberry Pi Pico)", None, FIVE_TO_EIGHT, false),
+    chip(0x0483, 0x5740, None, "CDC-ACM (STM32 Virtual COM Port)", None, FIVE_TO_EIGHT, false),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usb_port_info(vid: u16, pid: u16) -> UsbPortInfo {
+        UsbPortInfo {
+            vid,
+            pid,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "usbportinfo-details")]
+    fn chip_lookup_distinguishes_device_releases() {
+        let name = |vid, pid, bcd_device| {
+            let info = UsbPortInfo {
+                bcd_device,
+                ..usb_port_info(vid, pid)
+            };
+            info.chip().map(|chip| chip.name)
+        };
+
+        assert_eq!(name(0x0403, 0x6010, Some(0x0500)), Some("FT2232C"));
+        assert_eq!(name(0x0403, 0x6010, Some(0x0700)), Some("FT2232H"));
+        assert_eq!(name(0x0403, 0x6010, None), Some("FT2232H"));
+        assert_eq!(name(0x067b, 0x2303, Some(0x0300)), Some("PL2303HX"));
+        assert_eq!(name(0x067b, 0x2303, Some(0x0100)), Some("PL2303"));
+        assert_eq!(name(0x1234, 0x5678, None), None);
+    }
+
+    #[test]
+    fn chip_check_rejects_unsupported_settings() {
+        let ch340 = usb_port_info(0x1a86, 0x7523).chip().unwrap();
+        assert!(ch340.check(&crate::new("/dev/ttyUSB0", 115_200)).is_ok());
+
+        let error = ch340
+            .check(&crate::new("/dev/ttyUSB0", 3_000_000))
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            error.description,
+            "CH340 does not support 3000000 baud (maximum is 2000000 baud)"
+        );
+
+        let error = ch340
+            .check(&crate::new("/dev/ttyUSB0", 115_200).flow_control(FlowControl::Hardware))
+            .unwrap_err();
+        assert_eq!(
+            error.description,
+            "CH340 does not support hardware flow control"
+        );
+
+        let ft232r = usb_port_info(0x0403, 0x6001).chip().unwrap();
+        let error = ft232r
+            .check(&crate::new("/dev/ttyUSB0", 115_200).data_bits(DataBits::Five))
+            .unwrap_err();
+        assert_eq!(error.description, "FT232R does not support 5 data bits");
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt;
+
+use crate::{available_ports, Error, ErrorKind, Result, SerialPortInfo, SerialPortType};
+use crate::{SerialPortBuilder, UsbPortInfo};
+
+/// Criteria for picking a USB serial port by its identity instead of its device path
+///
+/// Device paths like `/dev/ttyUSB0` or `COM3` may change between reboots or when re-plugging an
+/// adapter. The identity of a USB device is stable and can be used for finding the port with
+/// [`find`] or [`SerialPortBuilder::from_usb`] instead. All criteria which are set have to match.
+///
+/// ```no_run
+/// use serialport::UsbPortFilter;
+///
+/// let filter = UsbPortFilter::new().vid(0x0403).pid(0x6001).serial_number("A50285BI");
+/// let port = serialport::SerialPortBuilder::from_usb(&filter, 115_200)
+///     .expect("No unique matching port found")
+///     .open()
+///     .expect("Failed to open port");
+/// # let _ = port;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsbPortFilter {
+    vid: Option<u16>,
+    pid: Option<u16>,
+    serial_number: Option<String>,
+    manufacturer: Option<String>,
+    product: Option<String>,
+    #[cfg(feature = "usbportinfo-interface")]
+    interface: Option<u8>,
+}
+
+impl UsbPortFilter {
+    /// Creates a filter matching any USB serial port
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match the vendor ID
+    #[must_use]
+    pub fn vid(mut self, vid: u16) -> Self {
+        self.vid = Some(vid);
+        self
+    }
+
+    /// Match the product ID
+    #[must_use]
+    pub fn pid(mut self, pid: u16) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// Match the serial number exactly
+    #[must_use]
+    pub fn serial_number<T: Into<String>>(mut self, serial_number: T) -> Self {
+        self.serial_number = Some(serial_number.into());
+        self
+    }
+
+    /// Match ports whose manufacturer contains the given string (ignoring case)
+    #[must_use]
+    pub fn manufacturer<T: Into<String>>(mut self, manufacturer: T) -> Self {
+        self.manufacturer = Some(manufacturer.into());
+        self
+    }
+
+    /// Match ports whose product name contains the given string (ignoring case)
+    #[must_use]
+    pub fn product<T: Into<String>>(mut self, product: T) -> Self {
+        self.product = Some(product.into());
+        self
+    }
+
+    /// Match the interface number of a composite device (like an FTDI quad port adapter)
+    #[cfg(feature = "usbportinfo-interface")]
+    #[must_use]
+    pub fn interface(mut self, interface: u8) -> Self {
+        self.interface = Some(interface);
+        self
+    }
+
+    /// Checks whether the given USB information matches all criteria of this filter
+    pub fn matches(&self, info: &UsbPortInfo) -> bool {
+        fn contains_ignore_case(haystack: &Option<String>, needle: &Option<String>) -> bool {
+            match (haystack, needle) {
+                (_, None) => true,
+                (Some(haystack), Some(needle)) => haystack
+                    .to_lowercase()
+                    .contains(needle.to_lowercase().as_str()),
+                (None, Some(_)) => false,
+            }
+        }
+
+        self.vid.map_or(true, |vid| vid == info.vid)
+            && self.pid.map_or(true, |pid| pid == info.pid)
+            && self
+                .serial_number
+                .as_ref()
+                .map_or(true, |sn| info.serial_number.as_ref() == Some(sn))
+            && contains_ignore_case(&info.manufacturer, &self.manufacturer)
+            && contains_ignore_case(&info.product, &self.product)
+            && self.matches_interface(info)
+    }
+
+    #[cfg(feature = "usbportinfo-interface")]
+    fn matches_interface(&self, info: &UsbPortInfo) -> bool {
+        self.interface
+            .map_or(true, |interface| info.interface == Some(interface))
+    }
+
+    #[cfg(not(feature = "usbportinfo-interface"))]
+    fn matches_interface(&self, _info: &UsbPortInfo) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for UsbPortFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut criteria = Vec::new();
+        if let Some(vid) = self.vid {
+            criteria.push(format!("vid {:04x}", vid));
+        }
+        if let Some(pid) = self.pid {
+            criteria.push(format!("pid {:04x}", pid));
+        }
+        if let Some(ref serial_number) = self.serial_number {
+            criteria.push(format!("serial number {:?}", serial_number));
+        }
+        if let Some(ref manufacturer) = self.manufacturer {
+            criteria.push(format!("manufacturer containing {:?}", manufacturer));
+        }
+        if let Some(ref product) = self.product {
+            criteria.push(format!("product containing {:?}", product));
+        }
+        #[cfg(feature = "usbportinfo-interface")]
+        if let Some(interface) = self.interface {
+            criteria.push(format!("interface {:02x}", interface));
+        }
+
+        if criteria.is_empty() {
+            write!(f, "any USB port")
+        } else {
+            write!(f, "{}", criteria.join(", "))
+        }
+    }
+}
+
+/// Describes a port for listing it as a candidate in error messages.
+fn describe_candidate(port: &SerialPortInfo) -> String {
+    match port.port_type {
+        SerialPortType::UsbPort(ref info) => {
+            let mut description = format!("{} ({:04x}:{:04x}", port.port_name, info.vid, info.pid);
+            for value in [&info.manufacturer, &info.product]
+                .iter()
+                .copied()
+                .flatten()
+            {
+                description.push(' ');
+                description.push_str(value);
+            }
+            if let Some(ref serial_number) = info.serial_number {
+                description.push_str(&format!(", serial number {:?}", serial_number));
+            }
+            #[cfg(feature = "usbportinfo-interface")]
+            if let Some(interface) = info.interface {
+                description.push_str(&format!(", interface {:02x}", interface));
+            }
+            description.push(')');
+            description
+        }
+        _ => port.port_name.clone(),
+    }
+}
+
+fn describe_candidates<'a>(ports: impl Iterator<Item = &'a SerialPortInfo>) -> String {
+    let candidates: Vec<_> = ports.map(describe_candidate).collect();
+    if candidates.is_empty() {
+        "none".to_string()
+    } else {
+        candidates.join(", ")
+    }
+}
+
+/// Picks the single port matching `filter` from the given list of ports.
+fn select(filter: &UsbPortFilter, ports: Vec<SerialPortInfo>) -> Result<SerialPortInfo> {
+    let is_match = |port: &SerialPortInfo| match port.port_type {
+        SerialPortType::UsbPort(ref info) => filter.matches(info),
+        _ => false,
+    };
+    #[allow(unused_mut)]
+    let mut matches: Vec<&SerialPortInfo> = ports.iter().filter(|p| is_match(p)).collect();
+
+    // Every device shows up twice on macOS: as a call-out device (`/dev/cu.*`) for initiating
+    // connections and as a dial-in device (`/dev/tty.*`). Prefer the call-out device as this is
+    // the one to use for talking to a device.
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    if matches.iter().any(|p| p.port_name.starts_with("/dev/cu.")) {
+        matches.retain(|p| !p.port_name.starts_with("/dev/tty."));
+    }
+
+    match matches.len() {
+        1 => Ok(matches[0].clone()),
+        0 => Err(Error::new(
+            ErrorKind::NoDevice,
+            format!(
+                "No serial port matches {}; available USB ports: {}",
+                filter,
+                describe_candidates(
+                    ports
+                        .iter()
+                        .filter(|p| matches!(p.port_type, SerialPortType::UsbPort(_)))
+                ),
+            ),
+        )),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Multiple serial ports match {}: {}",
+                filter,
+                describe_candidates(matches.into_iter()),
+            ),
+        )),
+    }
+}
+
+/// Finds the serial port matching the given USB identity
+///
+/// The ports are looked up via [`available_ports`]. Exactly one port has to match all criteria
+/// of `filter`.
+///
+/// ## Errors
+///
+/// * `NoDevice` if no port matches. The description lists the available USB ports.
+/// * `InvalidInput` if more than one port matches. The description lists the matching ports.
+/// * Any error from enumerating the ports.
+pub fn find(filter: &UsbPortFilter) -> Result<SerialPortInfo> {
+    select(filter, available_ports()?)
+}
+
+impl SerialPortBuilder {
+    /// Construct a builder for the serial port matching the given USB identity
+    ///
+    /// This looks up the current device path of the port with [`find`] and is otherwise the same
+    /// as [`new`](crate::new).
+    ///
+    /// ## Errors
+    ///
+    /// See [`find`].
+    pub fn from_usb(filter: &UsbPortFilter, baud_rate: u32) -> Result<SerialPortBuilder> {
+        find(filter).map(|info| crate::new(info.port_name, baud_rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The struct update only has an effect with features adding fields to `UsbPortInfo`.
+    #[allow(clippy::needless_update)]
+    fn usb_port(name: &str, vid: u16, pid: u16, serial_number: Option<&str>) -> SerialPortInfo {
+        SerialPortInfo {
+            port_name: name.to_string(),
+            port_type: SerialPortType::UsbPort(UsbPortInfo {
+                vid,
+                pid,
+                serial_number: serial_number.map(String::from),
+                manufacturer: Some("FTDI".to_string()),
+                product: Some("FT232R USB UART".to_string()),
+                #[cfg(feature = "usbportinfo-interface")]
+                interface: Some(0),
+                ..Default::default()
+            }),
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: Vec::new(),
+            #[cfg(feature = "serialportinfo-details")]
+            driver: None,
+            #[cfg(feature = "serialportinfo-details")]
+            sysfs_path: None,
+        }
+    }
+
+    fn ports() -> Vec<SerialPortInfo> {
+        vec![
+            SerialPortInfo {
+                port_name: "/dev/ttyS0".to_string(),
+                port_type: SerialPortType::PciPort,
+                #[cfg(feature = "serialportinfo-details")]
+                aliases: Vec::new(),
+                #[cfg(feature = "serialportinfo-details")]
+                driver: None,
+                #[cfg(feature = "serialportinfo-details")]
+                sysfs_path: None,
+            },
+            usb_port("/dev/ttyUSB0", 0x0403, 0x6001, Some("A50285BI")),
+            usb_port("/dev/ttyUSB1", 0x0403, 0x6001, Some("A10K3XYZ")),
+            usb_port("/dev/ttyACM0", 0x2341, 0x0043, None),
+        ]
+    }
+
+    #[test]
+    fn select_unique_match() {
+        let filter = UsbPortFilter::new().vid(0x0403).serial_number("A10K3XYZ");
+        let port = select(&filter, ports()).unwrap();
+        assert_eq!(port.port_name, "/dev/ttyUSB1");
+
+        let filter = UsbPortFilter::new()
+            .product("ft232r")
+            .serial_number("A50285BI");
+        let port = select(&filter, ports()).unwrap();
+        assert_eq!(port.port_name, "/dev/ttyUSB0");
+    }
+
+    #[test]
+    fn select_missing_lists_candidates() {
+        let filter = UsbPortFilter::new().vid(0x10c4);
+        let error = select(&filter, ports()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NoDevice);
+        assert!(error.description.contains("vid 10c4"));
+        assert!(error.description.contains("/dev/ttyUSB0"));
+        assert!(error.description.contains("/dev/ttyACM0"));
+        assert!(!error.description.contains("/dev/ttyS0"));
+    }
+
+    #[test]
+    fn select_ambiguous_lists_matches() {
+        let filter = UsbPortFilter::new().vid(0x0403).pid(0x6001);
+        let error = select(&filter, ports()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(error.description.contains("/dev/ttyUSB0"));
+        assert!(error.description.contains("/dev/ttyUSB1"));
+        assert!(!error.description.contains("/dev/ttyACM0"));
+    }
+
+    #[test]
+    fn empty_filter_matches_any_usb_port() {
+        let filter = UsbPortFilter::new();
+        let info = UsbPortInfo {
+            vid: 0x1234,
+            pid: 0x5678,
+            serial_number: None,
+            manufacturer: None,
+            product: None,
+            #[cfg(feature = "usbportinfo-interface")]
+            interface: None,
+            #[cfg(feature = "usbportinfo-details")]
+            interface_description: None,
+            #[cfg(feature = "usbportinfo-details")]
+            bcd_device: None,
+            #[cfg(feature = "usbportinfo-details")]
+            bus_number: None,
+            #[cfg(feature = "usbportinfo-details")]
+            device_address: None,
+            #[cfg(feature = "usbportinfo-details")]
+            port_path: None,
+        };
+        assert!(filter.matches(&info));
+        assert!(!UsbPortFilter::new().manufacturer("FTDI").matches(&info));
+    }
+}
@@ -35,19 +35,43 @@
 use std::error::Error as StdError;
 use std::fmt;
 use std::io;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+#[cfg(feature = "error-details")]
+use std::sync::Arc;
 use std::time::Duration;
 
+mod access;
+pub use access::AccessDiagnostics;
+mod chip;
+pub use chip::ChipInfo;
+mod filter;
+pub use filter::{find, UsbPortFilter};
+
 #[cfg(unix)]
 mod posix;
+#[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+pub use posix::UdevEnumerator;
 #[cfg(unix)]
-pub use posix::{BreakDuration, TTYPort};
+pub use posix::{AdoptOptions, BreakDuration, TTYPort};
+#[cfg(target_os = "linux")]
+pub use posix::{SerialInfo, SysfsEnumerator};
 
 #[cfg(windows)]
 mod windows;
 #[cfg(windows)]
 pub use windows::COMPort;
 
+mod reconnect;
+mod strict;
+pub use reconnect::{Backoff, ReconnectEvent, ReconnectingPort};
+mod users;
+mod wait;
+pub use users::{port_users, PortProcess, PortUsers};
+
+mod watch;
+pub use watch::{PortEvent, PortWatcher};
+
 #[cfg(test)]
 pub(crate) mod tests;
 
@@ -60,12 +84,16 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// This list is intended to grow over time and it is not recommended to
 /// exhaustively match against it.
+///
+/// The kinds `PermissionDenied`, `Busy`, `Disconnected`, `HangUp`, `TimedOut` and `Unsupported`
+/// are available with the feature `error-details`. Without it, the errors documented with these
+/// kinds throughout this crate are reported as `Io` or `Unknown` like before.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorKind {
     /// The device is not available.
     ///
-    /// This could indicate that the device is in use by another process or was
-    /// disconnected while performing I/O.
+    /// This could indicate that the device does not exist or has no actual
+    /// hardware.
     NoDevice,
 
     /// A parameter was incorrect.
@@ -78,15 +106,78 @@ pub enum ErrorKind {
     ///
     /// The type of I/O error is determined by the inner `io::ErrorKind`.
     Io(io::ErrorKind),
+
+    /// The permissions for accessing the device are missing.
+    #[cfg(feature = "error-details")]
+    PermissionDenied,
+
+    /// The device is in use by another process (or in exclusive mode).
+    #[cfg(feature = "error-details")]
+    Busy,
+
+    /// The device was disconnected (like a USB serial adapter being unplugged).
+    #[cfg(feature = "error-details")]
+    Disconnected,
+
+    /// The carrier was lost (like a modem hanging up) while honoring carrier detect.
+    ///
+    /// See [`SerialPortBuilder::carrier_detect`].
+    #[cfg(feature = "error-details")]
+    HangUp,
+
+    /// The operation did not complete within its timeout.
+    #[cfg(feature = "error-details")]
+    TimedOut,
+
+    /// The operation is not supported by the device or its driver.
+    #[cfg(feature = "error-details")]
+    Unsupported,
+}
+
+/// Kinds of errors which are only told apart with the feature `error-details`. Without it, they
+/// are reported as the I/O errors they have been reported as before.
+impl ErrorKind {
+    #[cfg(feature = "error-details")]
+    pub(crate) const PERMISSION_DENIED: ErrorKind = ErrorKind::PermissionDenied;
+    #[cfg(not(feature = "error-details"))]
+    pub(crate) const PERMISSION_DENIED: ErrorKind = ErrorKind::Io(io::ErrorKind::PermissionDenied);
+
+    #[cfg(feature = "error-details")]
+    pub(crate) const DISCONNECTED: ErrorKind = ErrorKind::Disconnected;
+    #[cfg(not(feature = "error-details"))]
+    pub(crate) const DISCONNECTED: ErrorKind = ErrorKind::Io(io::ErrorKind::BrokenPipe);
+
+    #[cfg(feature = "error-details")]
+    pub(crate) const HANG_UP: ErrorKind = ErrorKind::HangUp;
+    #[cfg(not(feature = "error-details"))]
+    pub(crate) const HANG_UP: ErrorKind = ErrorKind::Io(io::ErrorKind::BrokenPipe);
+
+    #[cfg(feature = "error-details")]
+    pub(crate) const UNSUPPORTED: ErrorKind = ErrorKind::Unsupported;
+    #[cfg(not(feature = "error-details"))]
+    pub(crate) const UNSUPPORTED: ErrorKind = ErrorKind::Io(io::ErrorKind::Unsupported);
 }
 
 /// An error type for serial port operations
+///
+/// With the feature `error-details`, errors from the operating system keep their error code (see
+/// `Error::raw_os_error`) and the original error is available as
+/// [`source`](std::error::Error::source).
 #[derive(Debug, Clone)]
 pub struct Error {
     /// The kind of error this is
     pub kind: ErrorKind,
     /// A description of the error suitable for end-users
     pub description: String,
+    /// The error code of the operating system (`errno` or `GetLastError`)
+    #[cfg(feature = "error-details")]
+    raw_os_error: Option<i32>,
+    /// The error this one was caused by
+    #[cfg(feature = "error-details")]
+    source: Option<Arc<dyn StdError + Send + Sync>>,
+    /// Why the device could not be accessed
+    #[cfg(feature = "error-details")]
+    access_diagnostics: Option<Box<AccessDiagnostics>>,
 }
 
 impl Error {
@@ -95,13 +186,87 @@ impl Error {
         Error {
             kind,
             description: description.into(),
+            #[cfg(feature = "error-details")]
+            raw_os_error: None,
+            #[cfg(feature = "error-details")]
+            source: None,
+            #[cfg(feature = "error-details")]
+            access_diagnostics: None,
         }
     }
 
+    /// Attaches the error this one was caused by
+    #[cfg(feature = "error-details")]
+    #[must_use]
+    pub fn with_source<E: StdError + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    #[cfg(not(feature = "error-details"))]
+    #[must_use]
+    pub(crate) fn with_source<E>(self, _source: E) -> Self {
+        self
+    }
+
+    /// Replaces the description while keeping the kind, error code and source of the error.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    #[must_use]
+    pub(crate) fn with_description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Attaches the error code of the operating system.
+    #[cfg(feature = "error-details")]
+    #[must_use]
+    pub(crate) fn with_raw_os_error(mut self, code: i32) -> Self {
+        self.raw_os_error = Some(code);
+        self
+    }
+
+    #[cfg(not(feature = "error-details"))]
+    #[must_use]
+    pub(crate) fn with_raw_os_error(self, _code: i32) -> Self {
+        self
+    }
+
+    /// Attaches details on why the device could not be accessed.
+    #[cfg(feature = "error-details")]
+    #[cfg_attr(not(unix), allow(dead_code))]
+    #[must_use]
+    pub(crate) fn with_access_diagnostics(mut self, diagnostics: AccessDiagnostics) -> Self {
+        self.access_diagnostics = Some(Box::new(diagnostics));
+        self
+    }
+
+    #[cfg(not(feature = "error-details"))]
+    #[cfg_attr(not(unix), allow(dead_code))]
+    #[must_use]
+    pub(crate) fn with_access_diagnostics(self, _diagnostics: AccessDiagnostics) -> Self {
+        self
+    }
+
     /// Returns the corresponding `ErrorKind` for this error.
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    /// Returns the error code of the operating system (`errno` on POSIX platforms and
+    /// `GetLastError` on Windows) if this error originates from it
+    #[cfg(feature = "error-details")]
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.raw_os_error
+    }
+
+    /// Returns details on why the device could not be accessed
+    ///
+    /// These are available when opening a port on a POSIX platform fails with
+    /// `PermissionDenied` or because the port is a symbolic link to a missing device.
+    #[cfg(feature = "error-details")]
+    pub fn access_diagnostics(&self) -> Option<&AccessDiagnostics> {
+        self.access_diagnostics.as_deref()
+    }
 }
 
 impl fmt::Display for Error {
@@ -114,12 +279,48 @@ impl StdError for Error {
     fn description(&self) -> &str {
         &self.description
     }
+
+    #[cfg(feature = "error-details")]
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn StdError + 'static))
+    }
 }
 
 impl From<io::Error> for Error {
+    #[cfg(not(feature = "error-details"))]
     fn from(io_error: io::Error) -> Error {
         Error::new(ErrorKind::Io(io_error.kind()), format!("{}", io_error))
     }
+
+    #[cfg(feature = "error-details")]
+    fn from(io_error: io::Error) -> Error {
+        // Unwrap errors converted by `From<Error> for io::Error`.
+        if io_error
+            .get_ref()
+            .map_or(false, |inner| inner.is::<Error>())
+        {
+            let inner = io_error.into_inner().expect("checked above");
+            return *inner.downcast::<Error>().expect("checked above");
+        }
+
+        let kind = match io_error.raw_os_error() {
+            #[cfg(unix)]
+            Some(code) => posix::errno_kind(nix::errno::Errno::from_i32(code)),
+            _ => match io_error.kind() {
+                io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+                io::ErrorKind::TimedOut => ErrorKind::TimedOut,
+                io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+                kind => ErrorKind::Io(kind),
+            },
+        };
+        let mut error = Error::new(kind, io_error.to_string());
+        if let Some(code) = io_error.raw_os_error() {
+            error = error.with_raw_os_error(code);
+        }
+        error.with_source(io_error)
+    }
 }
 
 impl From<Error> for io::Error {
@@ -129,9 +330,26 @@ impl From<Error> for io::Error {
             ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
             ErrorKind::Unknown => io::ErrorKind::Other,
             ErrorKind::Io(kind) => kind,
+            #[cfg(feature = "error-details")]
+            ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            #[cfg(feature = "error-details")]
+            ErrorKind::Busy => io::ErrorKind::Other,
+            #[cfg(feature = "error-details")]
+            ErrorKind::Disconnected => io::ErrorKind::NotConnected,
+            #[cfg(feature = "error-details")]
+            ErrorKind::HangUp => io::ErrorKind::ConnectionAborted,
+            #[cfg(feature = "error-details")]
+            ErrorKind::TimedOut => io::ErrorKind::TimedOut,
+            #[cfg(feature = "error-details")]
+            ErrorKind::Unsupported => io::ErrorKind::Unsupported,
         };
 
-        io::Error::new(kind, error.description)
+        // Keep the error itself for converting it back losslessly.
+        #[cfg(feature = "error-details")]
+        let inner = error;
+        #[cfg(not(feature = "error-details"))]
+        let inner = error.description;
+        io::Error::new(kind, inner)
     }
 }
 
@@ -333,6 +551,48 @@ pub struct SerialPortBuilder {
     timeout: Duration,
     /// The state to set DTR to when opening the device
     dtr_on_open: Option<bool>,
+    /// Whether to hang up the modem control lines when the device gets closed
+    hangup_on_close: Option<bool>,
+    /// Whether to reject settings the USB adapter chip does not support
+    check_usb_chip: bool,
+    /// Maximum deviation of the effective from the requested baud rate
+    baud_rate_tolerance: Option<Percent>,
+    /// Whether to read back the settings after opening and fail if they differ
+    strict: bool,
+    /// Whether to claim exclusive access to the device
+    #[cfg_attr(not(unix), allow(dead_code))]
+    exclusive: bool,
+    /// Whether to open the device without changing its settings
+    keep_existing_settings: bool,
+    /// Whether to honor data carrier detect (DCD)
+    #[cfg_attr(not(unix), allow(dead_code))]
+    carrier_detect: bool,
+}
+
+/// A percentage which can be part of [`SerialPortBuilder`] and its `Eq` implementation
+#[derive(Debug, Clone, Copy)]
+struct Percent(f64);
+
+impl PartialEq for Percent {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Percent {}
+
+impl SerialPortBuilder {
+    /// Fails with `InvalidInput` if the tolerance set with
+    /// [`SerialPortBuilder::baud_rate_tolerance`] is negative or not finite.
+    pub(crate) fn verify_baud_rate_tolerance(&self) -> Result<()> {
+        match self.baud_rate_tolerance {
+            Some(Percent(percent)) if !percent.is_finite() || percent < 0.0 => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid baud rate tolerance of {} %", percent),
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl SerialPortBuilder {
@@ -415,6 +675,102 @@ impl SerialPortBuilder {
         self
     }
 
+    /// Set whether to hang up (drop DTR and RTS) when the last handle to the device gets closed
+    ///
+    /// Many boards reset when DTR drops. Disabling hang-up on close together with
+    /// [`preserve_dtr_on_open`](SerialPortBuilder::preserve_dtr_on_open) allows to close and
+    /// reopen a port without resetting the attached device. By default, the current setting of
+    /// the device is left untouched.
+    ///
+    /// This controls the `HUPCL` flag on POSIX platforms and has no effect on Windows.
+    #[must_use]
+    pub fn hangup_on_close(mut self, state: bool) -> Self {
+        self.hangup_on_close = Some(state);
+        self
+    }
+
+    /// Set whether to reject settings the USB serial adapter does not support when opening the
+    /// port
+    ///
+    /// The adapter chip is looked up with [`UsbPortInfo::chip`] and the settings are checked with
+    /// [`ChipInfo::check`]. Opening fails with `InvalidInput` instead of the driver silently
+    /// ignoring unsupported settings. Ports of unknown chips and ports which are not USB serial
+    /// adapters are opened as usual. By default, no check is performed.
+    #[must_use]
+    pub fn check_usb_chip(mut self, check: bool) -> Self {
+        self.check_usb_chip = check;
+        self
+    }
+
+    /// Set the maximum deviation in percent of the baud rate the UART actually produces from the
+    /// requested one
+    ///
+    /// Opening the port fails with `InvalidInput` if the deviation is larger. This is checked on
+    /// Linux for UARTs reporting their base baud rate (see [`TTYPort::effective_baud_rate`]) and
+    /// ignored otherwise. Adapters with fractional baud rate divisors (like FTDI ones) are not
+    /// checked either as their base baud rate does not tell the baud rates they produce. By
+    /// default, any deviation is accepted.
+    ///
+    /// Opening fails with `InvalidInput` on all platforms if `percent` is negative or not finite.
+    #[must_use]
+    pub fn baud_rate_tolerance(mut self, percent: f64) -> Self {
+        self.baud_rate_tolerance = Some(Percent(percent));
+        self
+    }
+
+    /// Set whether to verify that the device actually applied the settings when opening the port
+    ///
+    /// Some drivers silently ignore settings their hardware does not support. In strict mode, the
+    /// baud rate, data bits, parity, stop bits and flow control are read back after configuring
+    /// the port and opening fails with `Unsupported` listing each differing setting as requested
+    /// versus actual. By default, the settings are not verified.
+    ///
+    /// On POSIX platforms, the opened port stays in strict mode for its setters (see
+    /// [`TTYPort::set_strict`]). On Windows, only opening the port is verified and the setters of
+    /// `COMPort` apply settings without reading them back. On macOS and iOS, the baud rate can't be
+    /// read back and is never verified.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set whether to claim exclusive access to the device when opening it
+    ///
+    /// Exclusive access prevents other processes from opening the device. It can be changed
+    /// later with [`TTYPort::set_exclusive`]. This applies to POSIX platforms, where ports are
+    /// exclusive by default. COM ports on Windows are always exclusive.
+    #[must_use]
+    pub fn exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Open the device without changing any of its settings
+    ///
+    /// This allows to attach to a port configured by another component (like a console or a
+    /// vendor daemon). The baud rate, data bits, flow control, parity, stop bits and the state of
+    /// DTR from the builder are ignored and the current settings of the device are reported by
+    /// the getters of the port. Only the timeout and [`exclusive`](SerialPortBuilder::exclusive)
+    /// apply.
+    #[must_use]
+    pub fn keep_existing_settings(mut self) -> Self {
+        self.keep_existing_settings = true;
+        self
+    }
+
+    /// Set whether to honor data carrier detect (DCD)
+    ///
+    /// By default, the modem control lines are ignored (`CLOCAL`). When honoring carrier detect,
+    /// the port still opens without waiting for a carrier. Once the carrier drops, reading from
+    /// and writing to the port fail with `HangUp`. A device going away is reported the same way
+    /// then. This applies to POSIX platforms and has no effect on Windows.
+    #[must_use]
+    pub fn carrier_detect(mut self, honor: bool) -> Self {
+        self.carrier_detect = honor;
+        self
+    }
+
     /// Open a cross-platform interface to the port with the specified settings
     pub fn open(self) -> Result<Box<dyn SerialPort>> {
         #[cfg(unix)]
@@ -796,7 +1152,10 @@ impl fmt::Debug for dyn SerialPort {
 }
 
 /// Contains all possible USB information about a `SerialPort`
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Further fields may become available with features or future releases. Use
+/// `..Default::default()` for the remaining fields when constructing it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UsbPortInfo {
     /// Vendor ID
@@ -814,6 +1173,30 @@ pub struct UsbPortInfo {
     /// interface (as is the case on macOS), so you should recognize both interface numbers.
     #[cfg(feature = "usbportinfo-interface")]
     pub interface: Option<u8>,
+    /// Description string of the USB interface providing the serial port
+    #[cfg(feature = "usbportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interface_description: Option<String>,
+    /// Release number of the device (`bcdDevice`) as binary-coded decimal
+    #[cfg(feature = "usbportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bcd_device: Option<u16>,
+    /// Number of the bus the device is attached to
+    #[cfg(feature = "usbportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bus_number: Option<u8>,
+    /// Address of the device on its bus
+    #[cfg(feature = "usbportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub device_address: Option<u8>,
+    /// Chain of ports from the root hub to the device (like `1-2.3` for port 3 of the hub at port
+    /// 2 of bus 1)
+    ///
+    /// In contrast to the device address, this does not change when re-plugging the device into
+    /// the same port. It tells apart multiple devices with identical descriptors.
+    #[cfg(feature = "usbportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub port_path: Option<String>,
 }
 
 /// The physical type of a `SerialPort`
@@ -826,6 +1209,19 @@ pub enum SerialPortType {
     PciPort,
     /// The serial port is connected via Bluetooth
     BluetoothPort,
+    /// The serial port is a UART of the SoC or a legacy port on the platform bus (like the UARTs of
+    /// Raspberry Pi devices or `/dev/ttyS0` on x86)
+    #[cfg(feature = "serialporttype-details")]
+    PlatformPort {
+        /// Name of the kernel driver serving the port (Linux only)
+        driver: Option<String>,
+    },
+    /// The serial port is a pseudo terminal (like `/dev/pts/3`)
+    #[cfg(feature = "serialporttype-details")]
+    PseudoTerminal,
+    /// The serial port is provided by this device acting as a USB gadget (like `/dev/ttyGS0`)
+    #[cfg(feature = "serialporttype-details")]
+    UsbGadget,
     /// It can't be determined how the serial port is connected
     Unknown,
 }
@@ -838,6 +1234,96 @@ pub struct SerialPortInfo {
     pub port_name: String,
     /// The hardware device type that exposes this port
     pub port_type: SerialPortType,
+    /// Persistent names for this port
+    ///
+    /// These are symbolic links resolving to the port which do not change across reboots, like
+    /// `/dev/serial/by-id/usb-FTDI_FT232R_USB_UART_A50285BI-if00-port0`,
+    /// `/dev/serial/by-path/pci-0000:00:14.0-usb-0:2:1.0-port0` or board aliases like
+    /// `/dev/serial0`. Any of them can be used for opening the port. This is only populated on
+    /// Linux.
+    #[cfg(feature = "serialportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aliases: Vec<String>,
+    /// Name of the kernel driver serving this port (like `ftdi_sio`, `cp210x` or `cdc_acm`)
+    ///
+    /// This is only populated on Linux.
+    #[cfg(feature = "serialportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub driver: Option<String>,
+    /// Path of the TTY device in sysfs
+    ///
+    /// This is only populated on Linux.
+    #[cfg(feature = "serialportinfo-details")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sysfs_path: Option<String>,
+}
+
+impl SerialPortInfo {
+    /// Returns the most stable name for opening this port
+    ///
+    /// This prefers an alias from `/dev/serial/by-id`, which identifies the attached device, over
+    /// one from `/dev/serial/by-path`, which identifies the physical port it is plugged into, over
+    /// any other alias. If there are no aliases, this is the port name.
+    ///
+    /// ```no_run
+    /// for info in serialport::available_ports().expect("Failed to list ports") {
+    ///     println!("{} is also known as {}", info.port_name, info.stable_name());
+    ///     let port = serialport::new(info.stable_name(), 115_200).open();
+    /// #   let _ = port;
+    /// }
+    /// ```
+    #[cfg(feature = "serialportinfo-details")]
+    pub fn stable_name(&self) -> &str {
+        ["/dev/serial/by-id/", "/dev/serial/by-path/", ""]
+            .iter()
+            .find_map(|prefix| self.aliases.iter().find(|a| a.starts_with(prefix)))
+            .unwrap_or(&self.port_name)
+    }
+
+    /// Returns a human-friendly description of this port
+    ///
+    /// USB ports are described by their product name and, with the feature `usbportinfo-details`,
+    /// the description of their interface (like `Quad RS232-HS - Interface B`). Other ports are
+    /// described by their type.
+    pub fn description(&self) -> String {
+        match self.port_type {
+            SerialPortType::UsbPort(ref info) => {
+                let name = info
+                    .product
+                    .clone()
+                    .or_else(|| info.manufacturer.clone())
+                    .unwrap_or_else(|| {
+                        format!("USB serial port {:04x}:{:04x}", info.vid, info.pid)
+                    });
+                #[cfg(feature = "usbportinfo-details")]
+                if let Some(ref interface) = info.interface_description {
+                    if *interface != name {
+                        return format!("{} - {}", name, interface);
+                    }
+                }
+                name
+            }
+            SerialPortType::PciPort => "PCI serial port".to_string(),
+            SerialPortType::BluetoothPort => "Bluetooth serial port".to_string(),
+            #[cfg(feature = "serialporttype-details")]
+            SerialPortType::PlatformPort {
+                driver: Some(ref driver),
+            } => format!("Platform serial port ({})", driver),
+            #[cfg(feature = "serialporttype-details")]
+            SerialPortType::PlatformPort { driver: None } => "Platform serial port".to_string(),
+            #[cfg(feature = "serialporttype-details")]
+            SerialPortType::PseudoTerminal => "Pseudo terminal".to_string(),
+            #[cfg(feature = "serialporttype-details")]
+            SerialPortType::UsbGadget => "USB gadget serial port".to_string(),
+            #[cfg(feature = "serialportinfo-details")]
+            SerialPortType::Unknown => match self.driver {
+                Some(ref driver) => format!("Serial port ({})", driver),
+                None => "Serial port".to_string(),
+            },
+            #[cfg(not(feature = "serialportinfo-details"))]
+            SerialPortType::Unknown => "Serial port".to_string(),
+        }
+    }
 }
 
 /// Construct a builder of `SerialPort` objects
@@ -867,25 +1353,159 @@ pub fn new<'a>(path: impl Into<std::borrow::Cow<'a, str>>, baud_rate: u32) -> Se
         // substantially larger area than the one benefitting from it, I finally decided to revert
         // this. Sorry for this back and forth, Christian.
         dtr_on_open: None,
+        hangup_on_close: None,
+        check_usb_chip: false,
+        baud_rate_tolerance: None,
+        strict: false,
+        exclusive: true,
+        keep_existing_settings: false,
+        carrier_detect: false,
     }
 }
 
+/// Options for enumerating serial ports with [`available_ports_with`]
+///
+/// The defaults enumerate the ports of the running system. Pointing the roots to a different
+/// directory tree allows for enumerating ports of a chroot or container, or for testing the
+/// enumeration against a fabricated tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumerationOptions {
+    /// Mount point of sysfs where serial devices are looked up in `class/tty` (Linux only)
+    pub sysfs_root: PathBuf,
+    /// Directory containing the device files (Linux and FreeBSD only)
+    pub dev_root: PathBuf,
+    /// Include ports without an actual UART (Linux only)
+    ///
+    /// The kernel registers legacy serial ports like `/dev/ttyS0` to `/dev/ttyS31` regardless of
+    /// the hardware present. These ports are skipped by default.
+    pub include_phantom_ports: bool,
+    /// Include pseudo terminals from `/dev/pts` (Linux only)
+    pub include_pseudo_terminals: bool,
+}
+
+impl EnumerationOptions {
+    /// Checks whether these options refer to the running system.
+    #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+    pub(crate) fn has_default_roots(&self) -> bool {
+        let default = Self::default();
+        self.sysfs_root == default.sysfs_root && self.dev_root == default.dev_root
+    }
+}
+
+impl Default for EnumerationOptions {
+    fn default() -> Self {
+        EnumerationOptions {
+            sysfs_root: PathBuf::from("/sys"),
+            dev_root: PathBuf::from("/dev"),
+            include_phantom_ports: false,
+            include_pseudo_terminals: false,
+        }
+    }
+}
+
+/// A way of enumerating serial ports
+///
+/// [`available_ports`] picks the best enumerator for the platform. On Linux, the enumerators for
+/// udev ([`UdevEnumerator`], with the `libudev` feature) and sysfs ([`SysfsEnumerator`]) can be
+/// selected explicitly. Both report the same ports and information, except for the serial number,
+/// manufacturer and product of USB ports: udev reports them as encoded in its properties (like
+/// with underscores instead of spaces), sysfs as the raw descriptor strings. Otherwise, the choice
+/// is about the dependency on libudev (like for static musl builds).
+///
+/// ```no_run
+/// # #[cfg(target_os = "linux")] {
+/// use serialport::{EnumerationOptions, PortEnumerator, SysfsEnumerator};
+///
+/// let ports = SysfsEnumerator
+///     .available_ports(&EnumerationOptions::default())
+///     .expect("Failed to list ports");
+/// # let _ = ports;
+/// # }
+/// ```
+pub trait PortEnumerator {
+    /// Returns a list of all serial ports found with the given options
+    fn available_ports(&self, options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>>;
+}
+
 /// Returns a list of all serial ports on system
 ///
 /// It is not guaranteed that these ports exist or are available even if they're
 /// returned by this function.
 pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
+    available_ports_with(&EnumerationOptions::default())
+}
+
+/// Returns the information about the serial port at `path`
+///
+/// This provides the same information as [`available_ports`] but for a single port given by the
+/// user (like `/dev/ttyUSB3` or a persistent alias like `/dev/serial/by-id/...`). Symbolic links
+/// are resolved on Unix systems and the `port_name` of the result is the actual device.
+///
+/// ```no_run
+/// let info = serialport::port_info("/dev/ttyUSB3").expect("Not a serial port");
+/// if let serialport::SerialPortType::UsbPort(usb) = info.port_type {
+///     println!("{:04x}:{:04x}", usb.vid, usb.pid);
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// * `NoDevice` if the path does not refer to a serial port.
+/// * `Io` if the path could not be resolved (like when it does not exist).
+pub fn port_info<P: AsRef<Path>>(path: P) -> Result<SerialPortInfo> {
+    #[cfg(target_os = "linux")]
+    return crate::posix::port_info(path.as_ref());
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let path = path.as_ref();
+        let canonical = path.canonicalize().ok();
+        available_ports()?
+            .into_iter()
+            .find(|info| {
+                let port_name = Path::new(&info.port_name);
+                port_name == path
+                    || (canonical.is_some() && port_name.canonicalize().ok() == canonical)
+            })
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NoDevice,
+                    format!("{} is not a serial port", path.display()),
+                )
+            })
+    }
+}
+
+/// Returns a list of all serial ports found with the given options
+///
+/// On Linux, ports are enumerated from sysfs instead of udev when using custom roots.
+///
+/// ```no_run
+/// use serialport::EnumerationOptions;
+///
+/// let options = EnumerationOptions {
+///     sysfs_root: "/srv/container/sys".into(),
+///     dev_root: "/srv/container/dev".into(),
+///     ..EnumerationOptions::default()
+/// };
+/// let ports = serialport::available_ports_with(&options).expect("Failed to list ports");
+/// # let _ = ports;
+/// ```
+pub fn available_ports_with(options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
     #[cfg(unix)]
-    return crate::posix::available_ports();
+    return crate::posix::available_ports_with(options);
 
     #[cfg(windows)]
-    return crate::windows::available_ports();
+    return crate::windows::available_ports_with(options);
 
     #[cfg(not(any(unix, windows)))]
-    Err(Error::new(
-        ErrorKind::Unknown,
-        "available_ports() not implemented for platform",
-    ))
+    {
+        let _ = options;
+        Err(Error::new(
+            ErrorKind::Unknown,
+            "available_ports() not implemented for platform",
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -907,5 +1527,142 @@ mod test {
         assert_eq!(builder.stop_bits, StopBits::One);
         assert_eq!(builder.timeout, Duration::ZERO);
         assert_eq!(builder.dtr_on_open, None);
+        assert_eq!(builder.hangup_on_close, None);
+        assert!(!builder.check_usb_chip);
+        assert_eq!(builder.baud_rate_tolerance, None);
+        assert!(!builder.strict);
+        assert!(builder.exclusive);
+        assert!(!builder.keep_existing_settings);
+        assert!(!builder.carrier_detect);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_baud_rate_tolerance() {
+        assert!(new("port_test_dummy", 9600)
+            .verify_baud_rate_tolerance()
+            .is_ok());
+        assert!(new("port_test_dummy", 9600)
+            .baud_rate_tolerance(0.0)
+            .verify_baud_rate_tolerance()
+            .is_ok());
+        for percent in [-1.0, f64::NAN, f64::INFINITY] {
+            let error = new("port_test_dummy", 9600)
+                .baud_rate_tolerance(percent)
+                .open()
+                .unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "error-details"))]
+    fn error_keeps_4x_behavior() {
+        let error = Error {
+            kind: ErrorKind::NoDevice,
+            description: "No such device".to_string(),
+        };
+        let io_error = io::Error::from(error);
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+        let error = Error::from(io_error);
+        assert_eq!(error.kind(), ErrorKind::Io(io::ErrorKind::NotFound));
+
+        #[cfg(unix)]
+        {
+            use nix::errno::Errno;
+            assert_eq!(
+                Error::from(Errno::EACCES).kind(),
+                ErrorKind::Io(io::ErrorKind::PermissionDenied)
+            );
+            assert_eq!(
+                Error::from(Errno::ETIMEDOUT).kind(),
+                ErrorKind::Io(io::ErrorKind::TimedOut)
+            );
+            assert_eq!(Error::from(Errno::ENOTTY).kind(), ErrorKind::Unknown);
+            assert_eq!(Error::from(Errno::EBUSY).kind(), ErrorKind::Unknown);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "error-details")]
+    fn error_io_round_trip() {
+        let error = Error::new(ErrorKind::Busy, "Device or resource busy").with_raw_os_error(16);
+        let io_error = io::Error::from(error);
+        assert_eq!(io_error.kind(), io::ErrorKind::Other);
+        assert_eq!(io_error.to_string(), "Device or resource busy");
+
+        let error = Error::from(io_error);
+        assert_eq!(error.kind(), ErrorKind::Busy);
+        assert_eq!(error.description, "Device or resource busy");
+        assert_eq!(error.raw_os_error(), Some(16));
+    }
+
+    #[test]
+    #[cfg(feature = "error-details")]
+    fn error_from_io_error_keeps_source() {
+        let error = Error::from(io::Error::new(io::ErrorKind::TimedOut, "no response"));
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+        assert_eq!(error.raw_os_error(), None);
+        let source = error.source().expect("no source");
+        assert_eq!(source.to_string(), "no response");
+
+        #[cfg(unix)]
+        {
+            let error = Error::from(io::Error::from_raw_os_error(nix::libc::EACCES));
+            assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+            assert_eq!(error.raw_os_error(), Some(nix::libc::EACCES));
+        }
+    }
+
+    #[test]
+    fn port_info_description() {
+        let mut info = UsbPortInfo {
+            vid: 0x0403,
+            pid: 0x6011,
+            manufacturer: Some("FTDI".to_string()),
+            ..Default::default()
+        };
+        let port = |port_type: SerialPortType| SerialPortInfo {
+            port_name: "/dev/ttyUSB0".to_string(),
+            port_type,
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: Vec::new(),
+            #[cfg(feature = "serialportinfo-details")]
+            driver: None,
+            #[cfg(feature = "serialportinfo-details")]
+            sysfs_path: None,
+        };
+
+        assert_eq!(
+            port(SerialPortType::UsbPort(info.clone())).description(),
+            "FTDI"
+        );
+        info.product = Some("Quad RS232-HS".to_string());
+        assert_eq!(
+            port(SerialPortType::UsbPort(info.clone())).description(),
+            "Quad RS232-HS"
+        );
+        #[cfg(feature = "usbportinfo-details")]
+        {
+            info.interface_description = Some("Quad RS232-HS".to_string());
+            assert_eq!(
+                port(SerialPortType::UsbPort(info.clone())).description(),
+                "Quad RS232-HS"
+            );
+            info.interface_description = Some("Channel B".to_string());
+            assert_eq!(
+                port(SerialPortType::UsbPort(info)).description(),
+                "Quad RS232-HS - Channel B"
+            );
+        }
+
+        assert_eq!(port(SerialPortType::Unknown).description(), "Serial port");
+        #[cfg(feature = "serialportinfo-details")]
+        {
+            let unknown = SerialPortInfo {
+                driver: Some("serial".to_string()),
+                ..port(SerialPortType::Unknown)
+            };
+            assert_eq!(unknown.description(), "Serial port (serial)");
+        }
     }
 }
@@ -26,6 +26,8 @@ cfg_if! {
     }
 }
 
+#[cfg(target_os = "linux")]
+use crate::PortEnumerator;
 #[cfg(any(
     target_os = "freebsd",
     target_os = "ios",
@@ -33,18 +35,26 @@ cfg_if! {
     target_os = "macos"
 ))]
 use crate::SerialPortType;
-#[cfg(any(target_os = "ios", target_os = "linux", target_os = "macos"))]
+#[cfg(any(
+    target_os = "ios",
+    all(target_os = "linux", not(target_env = "musl"), feature = "libudev"),
+    target_os = "macos"
+))]
 use crate::UsbPortInfo;
+use crate::{EnumerationOptions, Result, SerialPortInfo};
 #[cfg(any(
     target_os = "android",
     target_os = "ios",
-    all(target_os = "linux", not(target_env = "musl"), feature = "libudev"),
+    target_os = "linux",
     target_os = "macos",
     target_os = "netbsd",
     target_os = "openbsd",
 ))]
 use crate::{Error, ErrorKind};
-use crate::{Result, SerialPortInfo};
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
 
 /// Retrieves the udev property value named by `key`. If the value exists, then it will be
 /// converted to a String, otherwise None will be returned.
@@ -132,6 +142,16 @@ fn port_type(d: &libudev::Device) -> Result<SerialPortType> {
                 #[cfg(feature = "usbportinfo-interface")]
                 interface: udev_hex_property_as_int(d, "ID_USB_INTERFACE_NUM", &u8::from_str_radix)
                     .ok(),
+                #[cfg(feature = "usbportinfo-details")]
+                interface_description: None,
+                #[cfg(feature = "usbportinfo-details")]
+                bcd_device: None,
+                #[cfg(feature = "usbportinfo-details")]
+                bus_number: None,
+                #[cfg(feature = "usbportinfo-details")]
+                device_address: None,
+                #[cfg(feature = "usbportinfo-details")]
+                port_path: None,
             }))
         }
         Some("pci") => {
@@ -167,23 +187,126 @@ fn port_type(d: &libudev::Device) -> Result<SerialPortType> {
                         &u8::from_str_radix,
                     )
                     .ok(),
+                    #[cfg(feature = "usbportinfo-details")]
+                    interface_description: None,
+                    #[cfg(feature = "usbportinfo-details")]
+                    bcd_device: None,
+                    #[cfg(feature = "usbportinfo-details")]
+                    bus_number: None,
+                    #[cfg(feature = "usbportinfo-details")]
+                    device_address: None,
+                    #[cfg(feature = "usbportinfo-details")]
+                    port_path: None,
                 }))
             } else {
                 Ok(SerialPortType::PciPort)
             }
         }
         None if is_rfcomm(d) => Ok(SerialPortType::BluetoothPort),
+        None if is_usb_gadget(d) => Ok(usb_gadget_type()),
         None => find_usb_interface_from_parents(d.parent())
             .and_then(get_modalias_from_device)
             .as_deref()
             .and_then(parse_modalias)
-            .map_or(Ok(SerialPortType::Unknown), |port_info| {
-                Ok(SerialPortType::UsbPort(port_info))
-            }),
+            .map_or_else(
+                || Ok(udev_platform_port_type(d)),
+                |port_info| Ok(SerialPortType::UsbPort(port_info)),
+            ),
         _ => Ok(SerialPortType::Unknown),
     }
 }
 
+/// Classifies ports of SoC UARTs (like the AMBA UARTs of Raspberry Pi devices), legacy ports on
+/// the platform or PNP bus and PCI ports without udev bus information.
+#[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+fn udev_platform_port_type(d: &libudev::Device) -> SerialPortType {
+    match udev_hardware_parent(d) {
+        Some(parent) => match parent.subsystem().and_then(OsStr::to_str) {
+            Some("amba") | Some("platform") | Some("pnp") => platform_port_type(
+                parent
+                    .driver()
+                    .map(|driver| driver.to_string_lossy().into_owned()),
+            ),
+            Some("pci") => SerialPortType::PciPort,
+            _ => SerialPortType::Unknown,
+        },
+        None => SerialPortType::Unknown,
+    }
+}
+
+/// Returns the hardware device of the TTY device `d`, skipping the port and controller devices
+/// on the `serial-base` bus like the sysfs enumeration does.
+#[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+fn udev_hardware_parent(d: &libudev::Device) -> Option<libudev::Device> {
+    let mut parent = d.parent()?;
+    while parent.subsystem().and_then(OsStr::to_str) == Some(SERIAL_BASE_SUBSYSTEM) {
+        parent = parent.parent()?;
+    }
+    Some(parent)
+}
+
+/// Retrieves the sysfs attribute named by `key` as String.
+#[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+fn udev_attribute_as_string(d: &libudev::Device, key: &str) -> Option<String> {
+    d.attribute_value(key)
+        .and_then(OsStr::to_str)
+        .map(|s| s.trim().to_string())
+}
+
+/// Looks up the closest ancestor of `d` with the given device type (like `usb_device`).
+#[cfg(all(
+    target_os = "linux",
+    not(target_env = "musl"),
+    feature = "libudev",
+    any(feature = "usbportinfo-details", feature = "usbportinfo-interface")
+))]
+fn udev_ancestor_with_devtype(d: &libudev::Device, devtype: &str) -> Option<libudev::Device> {
+    let mut current = d.parent();
+    // limit the query depth
+    for _ in 0..8 {
+        let device = current?;
+        if device.devtype().and_then(OsStr::to_str) == Some(devtype) {
+            return Some(device);
+        }
+        current = device.parent();
+    }
+    None
+}
+
+/// Completes `info` with the USB topology and interface details from the USB interface and device
+/// `d` belongs to. These are read from sysfs attributes like the sysfs enumeration does.
+#[cfg(all(
+    target_os = "linux",
+    not(target_env = "musl"),
+    feature = "libudev",
+    any(feature = "usbportinfo-details", feature = "usbportinfo-interface")
+))]
+fn udev_add_usb_details(d: &libudev::Device, info: &mut UsbPortInfo) {
+    if let Some(interface) = udev_ancestor_with_devtype(d, "usb_interface") {
+        #[cfg(feature = "usbportinfo-details")]
+        {
+            info.interface_description = udev_attribute_as_string(&interface, "interface");
+        }
+        #[cfg(feature = "usbportinfo-interface")]
+        if let Some(number) = udev_attribute_as_string(&interface, "bInterfaceNumber")
+            .and_then(|s| u8::from_str_radix(&s, 16).ok())
+        {
+            info.interface = Some(number);
+        }
+    }
+    #[cfg(feature = "usbportinfo-details")]
+    if let Some(device) = udev_ancestor_with_devtype(d, "usb_device") {
+        info.bcd_device = udev_attribute_as_string(&device, "bcdDevice")
+            .and_then(|s| u16::from_str_radix(&s, 16).ok());
+        info.bus_number = udev_attribute_as_string(&device, "busnum").and_then(|s| s.parse().ok());
+        info.device_address =
+            udev_attribute_as_string(&device, "devnum").and_then(|s| s.parse().ok());
+        info.port_path = device
+            .sysname()
+            .map(|name| name.to_string_lossy().into_owned());
+    }
+}
+
 #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
 fn find_usb_interface_from_parents(parent: Option<libudev::Device>) -> Option<libudev::Device> {
     let mut p = parent?;
@@ -262,9 +385,439 @@ fn parse_modalias(moda: &str) -> Option<UsbPortInfo> {
                     .and_then(|interface| u8::from_str_radix(interface, 16).ok())
             })
         }),
+        #[cfg(feature = "usbportinfo-details")]
+        interface_description: None,
+        #[cfg(feature = "usbportinfo-details")]
+        bcd_device: None,
+        #[cfg(feature = "usbportinfo-details")]
+        bus_number: None,
+        #[cfg(feature = "usbportinfo-details")]
+        device_address: None,
+        #[cfg(feature = "usbportinfo-details")]
+        port_path: None,
     })
 }
 
+/// Directories below `/dev` which contain persistent symbolic links to serial devices. Besides the
+/// links maintained by udev in `/dev/serial`, board-specific aliases like `/dev/serial0` on
+/// Raspberry Pi devices reside directly in `/dev`.
+#[cfg(target_os = "linux")]
+const ALIAS_DIRS: [&str; 3] = ["serial/by-id", "serial/by-path", ""];
+
+/// Collects the symbolic links from [`ALIAS_DIRS`] below `dev_path` by the canonical path of the
+/// device they are resolving to. Nothing is collected without the `serialportinfo-details`
+/// feature, which provides [`SerialPortInfo::aliases`].
+#[cfg(target_os = "linux")]
+fn scan_aliases(dev_path: &Path) -> HashMap<PathBuf, Vec<String>> {
+    let mut aliases: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    if cfg!(not(feature = "serialportinfo-details")) {
+        return aliases;
+    }
+
+    for dir in ALIAS_DIRS.iter() {
+        let entries = match dev_path.join(dir).read_dir() {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        let mut links: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().map(|t| t.is_symlink()).unwrap_or(false))
+            .map(|e| e.path())
+            .collect();
+        links.sort();
+
+        for link in links {
+            // Skip links like `/dev/stdin` which point to the file descriptors of the current
+            // process and would resolve to its terminal.
+            match std::fs::read_link(&link) {
+                Ok(target) if !target.starts_with("/proc") => (),
+                _ => continue,
+            }
+            if let Ok(target) = link.canonicalize() {
+                aliases
+                    .entry(target)
+                    .or_default()
+                    .push(link.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    aliases
+}
+
+/// Looks up the aliases for the given device file from the result of [`scan_aliases`].
+#[cfg(all(target_os = "linux", feature = "serialportinfo-details"))]
+fn aliases_of(aliases: &HashMap<PathBuf, Vec<String>>, device_file: &Path) -> Vec<String> {
+    device_file
+        .canonicalize()
+        .ok()
+        .and_then(|path| aliases.get(&path))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Name prefix of the serial ports provided by a Linux USB gadget (like `ttyGS0`).
+#[cfg(target_os = "linux")]
+const USB_GADGET_PREFIX: &str = "ttyGS";
+
+/// Subsystem of the port and controller devices the serial core puts between a TTY and its
+/// hardware device (since Linux 6.5).
+#[cfg(target_os = "linux")]
+const SERIAL_BASE_SUBSYSTEM: &str = "serial-base";
+
+/// Type of SoC UARTs and legacy ports on the platform bus served by `driver`. They are reported
+/// as [`SerialPortType::Unknown`] without the feature `serialporttype-details`.
+#[cfg(all(target_os = "linux", feature = "serialporttype-details"))]
+fn platform_port_type(driver: Option<String>) -> SerialPortType {
+    SerialPortType::PlatformPort { driver }
+}
+
+#[cfg(all(target_os = "linux", not(feature = "serialporttype-details")))]
+fn platform_port_type(_driver: Option<String>) -> SerialPortType {
+    SerialPortType::Unknown
+}
+
+/// Type of pseudo terminals, see [`platform_port_type`] for the fallback.
+#[cfg(all(target_os = "linux", feature = "serialporttype-details"))]
+fn pseudo_terminal_type() -> SerialPortType {
+    SerialPortType::PseudoTerminal
+}
+
+#[cfg(all(target_os = "linux", not(feature = "serialporttype-details")))]
+fn pseudo_terminal_type() -> SerialPortType {
+    SerialPortType::Unknown
+}
+
+/// Type of ports provided by a USB gadget, see [`platform_port_type`] for the fallback.
+#[cfg(all(target_os = "linux", feature = "serialporttype-details"))]
+fn usb_gadget_type() -> SerialPortType {
+    SerialPortType::UsbGadget
+}
+
+#[cfg(all(target_os = "linux", not(feature = "serialporttype-details")))]
+fn usb_gadget_type() -> SerialPortType {
+    SerialPortType::Unknown
+}
+
+/// Lists the pseudo terminals from `pts` below the device root. They don't show up in sysfs.
+#[cfg(target_os = "linux")]
+fn pseudo_terminals(dev_root: &Path) -> Vec<SerialPortInfo> {
+    let entries = match dev_root.join("pts").read_dir() {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut terminals: Vec<(u32, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let number = entry.file_name().to_string_lossy().parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .collect();
+    terminals.sort();
+    terminals
+        .into_iter()
+        .map(|(_, path)| pseudo_terminal_info(&path))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn pseudo_terminal_info(path: &Path) -> SerialPortInfo {
+    SerialPortInfo {
+        port_name: path.to_string_lossy().into_owned(),
+        port_type: pseudo_terminal_type(),
+        #[cfg(feature = "serialportinfo-details")]
+        aliases: Vec::new(),
+        #[cfg(feature = "serialportinfo-details")]
+        driver: None,
+        #[cfg(feature = "serialportinfo-details")]
+        sysfs_path: None,
+    }
+}
+
+/// Looks up the information about the serial port at `path` the same way enumerating the ports
+/// does. Symbolic links get resolved and phantom ports and pseudo terminals are reported as well.
+#[cfg(target_os = "linux")]
+pub fn port_info(path: &Path) -> Result<SerialPortInfo> {
+    let options = EnumerationOptions {
+        include_phantom_ports: true,
+        include_pseudo_terminals: true,
+        ..EnumerationOptions::default()
+    };
+    port_info_with(path, &options)
+}
+
+#[cfg(target_os = "linux")]
+fn port_info_with(path: &Path, options: &EnumerationOptions) -> Result<SerialPortInfo> {
+    let device_file = path.canonicalize().map_err(|e| {
+        let description = format!("Failed to resolve {}: {}", path.display(), e);
+        Error::from(e).with_description(description)
+    })?;
+    let not_a_port = || {
+        Error::new(
+            ErrorKind::NoDevice,
+            format!("{} is not a serial port", path.display()),
+        )
+    };
+    let name = device_file.file_name().ok_or_else(not_a_port)?;
+
+    let pts = options.dev_root.join("pts").canonicalize().ok();
+    if device_file.parent() == pts.as_deref() && name.to_string_lossy().parse::<u32>().is_ok() {
+        return Ok(pseudo_terminal_info(&device_file));
+    }
+
+    let aliases = scan_aliases(&options.dev_root);
+    match lookup_port_info(name, options, &aliases) {
+        Some(info) if Path::new(&info.port_name).canonicalize().ok() == Some(device_file) => {
+            Ok(info)
+        }
+        _ => Err(not_a_port()),
+    }
+}
+
+/// Checks whether a port has no actual UART. The kernel registers the legacy ports `ttyS0` to
+/// `ttyS31` on x86 systems regardless of the hardware present and reports the UART type
+/// `PORT_UNKNOWN` for the missing ones.
+///
+/// The UART type is taken from the `type` attribute of the TTY device in sysfs. Ports whose type
+/// can't be determined are not considered to be phantom ports. Enumerating ports never opens them
+/// for querying the type as this could toggle their modem control lines.
+#[cfg(target_os = "linux")]
+fn is_phantom_port(type_attribute: Option<&str>) -> bool {
+    let uart_type: Option<nix::libc::c_int> = type_attribute.and_then(|t| t.trim().parse().ok());
+    uart_type == Some(super::ioctl::PORT_UNKNOWN)
+}
+
+/// Determines whether to skip a port of the given type as it is a phantom port. Only ports served
+/// by the serial core report a UART type which excludes USB and Bluetooth ports.
+#[cfg(target_os = "linux")]
+fn skip_phantom_port(
+    options: &EnumerationOptions,
+    port_type: &SerialPortType,
+    type_attribute: Option<&str>,
+) -> bool {
+    match port_type {
+        SerialPortType::UsbPort(_) | SerialPortType::BluetoothPort => false,
+        #[cfg(feature = "serialporttype-details")]
+        SerialPortType::UsbGadget | SerialPortType::PseudoTerminal => false,
+        _ => !options.include_phantom_ports && is_phantom_port(type_attribute),
+    }
+}
+
+/// Enumerates serial ports from sysfs
+///
+/// This is the default on Linux without the `libudev` feature and when using custom roots in
+/// [`EnumerationOptions`]. It reports the same information as [`UdevEnumerator`] (when available)
+/// but does not take udev's hardware database into account and reports the USB descriptor
+/// strings as they are.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SysfsEnumerator;
+
+#[cfg(target_os = "linux")]
+impl PortEnumerator for SysfsEnumerator {
+    fn available_ports(&self, options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
+        sysfs::available_ports(options)
+    }
+}
+
+/// Enumeration of serial ports from sysfs. This is the fallback for Linux systems without libudev
+/// and allows to run the enumeration against a fabricated file system tree.
+#[cfg(target_os = "linux")]
+mod sysfs {
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+    #[cfg(feature = "usbportinfo-details")]
+    use std::str::FromStr;
+
+    #[cfg(feature = "serialportinfo-details")]
+    use super::aliases_of;
+    use super::{
+        platform_port_type, pseudo_terminals, scan_aliases, skip_phantom_port, usb_gadget_type,
+        SERIAL_BASE_SUBSYSTEM, USB_GADGET_PREFIX,
+    };
+    use crate::{EnumerationOptions, Error, Result};
+    use crate::{SerialPortInfo, SerialPortType, UsbPortInfo};
+
+    fn is_rfcomm(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|o| o.to_str())
+            .map(|s| s.starts_with("rfcomm"))
+            .unwrap_or(false)
+    }
+
+    fn is_usb_gadget(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|o| o.to_str())
+            .map(|s| s.starts_with(USB_GADGET_PREFIX))
+            .unwrap_or(false)
+    }
+
+    fn read_file_to_trimmed_string(dir: &Path, file: &str) -> Option<String> {
+        let path = dir.join(file);
+        let mut s = String::new();
+        File::open(path).ok()?.read_to_string(&mut s).ok()?;
+        Some(s.trim().to_owned())
+    }
+
+    fn read_file_to_u16(dir: &Path, file: &str) -> Option<u16> {
+        u16::from_str_radix(&read_file_to_trimmed_string(dir, file)?, 16).ok()
+    }
+
+    #[cfg(feature = "usbportinfo-details")]
+    fn read_file_to_decimal<T: FromStr>(dir: &Path, file: &str) -> Option<T> {
+        read_file_to_trimmed_string(dir, file)?.parse().ok()
+    }
+
+    /// Determines the name of the driver bound to the given sysfs device.
+    fn read_driver(device_path: &Path) -> Option<String> {
+        let driver = device_path.join("driver").read_link().ok()?;
+        Some(driver.file_name()?.to_string_lossy().into_owned())
+    }
+
+    #[cfg(feature = "usbportinfo-interface")]
+    fn read_file_to_u8(dir: &Path, file: &str) -> Option<u8> {
+        u8::from_str_radix(&read_file_to_trimmed_string(dir, file)?, 16).ok()
+    }
+
+    fn read_subsystem(device_path: &Path) -> Option<String> {
+        let subsystem = device_path.join("subsystem").canonicalize().ok()?;
+        Some(subsystem.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Resolves the device of a TTY (its `device` link) to the actual hardware. Since Linux 6.5,
+    /// ports of serial core drivers are children of port and controller devices on the
+    /// `serial-base` bus which are skipped here.
+    pub(super) fn hardware_device(device_path: &Path) -> Option<PathBuf> {
+        let mut path = device_path.canonicalize().ok()?;
+        while read_subsystem(&path).as_deref() == Some(SERIAL_BASE_SUBSYSTEM) {
+            path = path.parent()?.to_path_buf();
+        }
+        Some(path)
+    }
+
+    pub(super) fn read_port_type(path: &Path) -> Option<SerialPortType> {
+        let path = path.canonicalize().ok()?;
+        let subsystem = read_subsystem(&path)?;
+
+        match subsystem.as_str() {
+            // Broadcom SoC UARTs (of Raspberry Pi devices), other SoC UARTs and legacy ports.
+            "amba" | "platform" | "pnp" => Some(platform_port_type(read_driver(&path))),
+            "pci" => Some(SerialPortType::PciPort),
+            "usb" => usb_port_type(&path),
+            "usb-serial" => usb_port_type(path.parent()?),
+            _ => None,
+        }
+    }
+
+    fn usb_port_type(interface_path: &Path) -> Option<SerialPortType> {
+        let info = read_usb_port_info(interface_path)?;
+        Some(SerialPortType::UsbPort(info))
+    }
+
+    fn read_usb_port_info(interface_path: &Path) -> Option<UsbPortInfo> {
+        let device_path = interface_path.parent()?;
+
+        let vid = read_file_to_u16(device_path, "idVendor")?;
+        let pid = read_file_to_u16(device_path, "idProduct")?;
+        #[cfg(feature = "usbportinfo-interface")]
+        let interface = read_file_to_u8(interface_path, "bInterfaceNumber");
+        let serial_number = read_file_to_trimmed_string(device_path, "serial");
+        let product = read_file_to_trimmed_string(device_path, "product");
+        let manufacturer = read_file_to_trimmed_string(device_path, "manufacturer");
+
+        Some(UsbPortInfo {
+            vid,
+            pid,
+            serial_number,
+            manufacturer,
+            product,
+            #[cfg(feature = "usbportinfo-interface")]
+            interface,
+            #[cfg(feature = "usbportinfo-details")]
+            interface_description: read_file_to_trimmed_string(interface_path, "interface"),
+            #[cfg(feature = "usbportinfo-details")]
+            bcd_device: read_file_to_u16(device_path, "bcdDevice"),
+            #[cfg(feature = "usbportinfo-details")]
+            bus_number: read_file_to_decimal(device_path, "busnum"),
+            #[cfg(feature = "usbportinfo-details")]
+            device_address: read_file_to_decimal(device_path, "devnum"),
+            #[cfg(feature = "usbportinfo-details")]
+            port_path: device_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+        })
+    }
+
+    /// Scans `class/tty` below the sysfs root for serial devices.
+    pub(super) fn available_ports(options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
+        let mut vec = Vec::new();
+        let sys_path = options.sysfs_root.join("class/tty");
+        let aliases = scan_aliases(&options.dev_root);
+        let entries = sys_path.read_dir().map_err(|e| {
+            let description = format!("Failed to read {}: {}", sys_path.display(), e);
+            Error::from(e).with_description(description)
+        })?;
+        for path in entries {
+            if let Some(info) = port_info(&path?.path(), options, &aliases) {
+                vec.push(info);
+            }
+        }
+        if options.include_pseudo_terminals {
+            vec.extend(pseudo_terminals(&options.dev_root));
+        }
+        Ok(vec)
+    }
+
+    /// Determines the information about the TTY class device at `raw_path` (like
+    /// `/sys/class/tty/ttyUSB0`). Returns `None` if this is not a serial port.
+    pub(super) fn port_info(
+        raw_path: &Path,
+        options: &EnumerationOptions,
+        aliases: &HashMap<PathBuf, Vec<String>>,
+    ) -> Option<SerialPortInfo> {
+        let port_type = if is_rfcomm(raw_path) {
+            SerialPortType::BluetoothPort
+        } else if is_usb_gadget(raw_path) {
+            usb_gadget_type()
+        } else {
+            read_port_type(&hardware_device(&raw_path.join("device"))?)?
+        };
+
+        // Generate the device file path `/dev/DEVICE` from the TTY class path
+        // `/sys/class/tty/DEVICE` and emit a serial device if this path exists. There are
+        // no further checks (yet) due to `Path::is_file` reports only regular files.
+        //
+        // See https://github.com/serialport/serialport-rs/issues/66 for details.
+        let device_file = options.dev_root.join(raw_path.file_name()?);
+        if !device_file.exists() {
+            return None;
+        }
+
+        let type_attribute = read_file_to_trimmed_string(raw_path, "type");
+        if skip_phantom_port(options, &port_type, type_attribute.as_deref()) {
+            return None;
+        }
+        #[cfg(not(feature = "serialportinfo-details"))]
+        let _ = aliases;
+
+        Some(SerialPortInfo {
+            port_name: device_file.to_string_lossy().to_string(),
+            port_type,
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: aliases_of(aliases, &device_file),
+            #[cfg(feature = "serialportinfo-details")]
+            driver: hardware_device(&raw_path.join("device")).and_then(|path| read_driver(&path)),
+            #[cfg(feature = "serialportinfo-details")]
+            sysfs_path: raw_path
+                .canonicalize()
+                .ok()
+                .map(|path| path.to_string_lossy().into_owned()),
+        })
+    }
+}
+
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 fn get_parent_device_by_type(
     device: io_object_t,
@@ -372,6 +925,16 @@ fn port_type(service: io_object_t) -> SerialPortType {
             interface: get_int_property(usb_device, "bInterfaceNumber")
                 .map(|x| x as u8)
                 .ok(),
+            #[cfg(feature = "usbportinfo-details")]
+            interface_description: None,
+            #[cfg(feature = "usbportinfo-details")]
+            bcd_device: None,
+            #[cfg(feature = "usbportinfo-details")]
+            bus_number: None,
+            #[cfg(feature = "usbportinfo-details")]
+            device_address: None,
+            #[cfg(feature = "usbportinfo-details")]
+            port_path: None,
         })
     } else if get_parent_device_by_type(service, bluetooth_device_class_name).is_some() {
         SerialPortType::BluetoothPort
@@ -384,7 +947,7 @@ cfg_if! {
     if #[cfg(any(target_os = "ios", target_os = "macos"))] {
         /// Scans the system for serial ports and returns a list of them.
         /// The `SerialPortInfo` struct contains the name of the port which can be used for opening it.
-        pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
+        pub fn available_ports_with(_options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
             use mach2::kern_return::KERN_SUCCESS;
             use mach2::port::{mach_port_t, MACH_PORT_NULL};
 
@@ -481,6 +1044,12 @@ cfg_if! {
                                         vec.push(SerialPortInfo {
                                             port_name: path,
                                             port_type: port_type(modem_service),
+                                            #[cfg(feature = "serialportinfo-details")]
+                                            aliases: Vec::new(),
+                                            #[cfg(feature = "serialportinfo-details")]
+                                            driver: None,
+                                            #[cfg(feature = "serialportinfo-details")]
+                                            sysfs_path: None,
                                         });
                                     }
                                     None => return Err(Error::new(ErrorKind::Unknown, format!("Failed to get string value for {}", key))),
@@ -505,171 +1074,147 @@ cfg_if! {
                 .unwrap_or(false)
         }
 
+        fn is_usb_gadget(device: &libudev::Device) -> bool {
+            device
+                .sysname()
+                .and_then(|o| o.to_str())
+                .map(|s| s.starts_with(USB_GADGET_PREFIX))
+                .unwrap_or(false)
+        }
+
         /// Scans the system for serial ports and returns a list of them.
         /// The `SerialPortInfo` struct contains the name of the port
         /// which can be used for opening it.
-        pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
-            let mut vec = Vec::new();
-            if let Ok(context) = libudev::Context::new() {
-                let mut enumerator = libudev::Enumerator::new(&context)?;
-                enumerator.match_subsystem("tty")?;
-                let devices = enumerator.scan_devices()?;
-                for d in devices {
-                    if let Some(devnode) = d.devnode().and_then(|o| o.to_str()) {
-                        let parent = d.parent();
-                        if parent.is_some() || is_rfcomm(&d) {
-                            if let Some(driver) = parent.as_ref().and_then(|d| d.driver()) {
-                                if driver == "serial8250" && crate::new(devnode, 9600).open().is_err() {
-                                    continue;
-                                }
-                            }
-
-                            // Stop bubbling up port_type errors here so problematic ports are just
-                            // skipped instead of causing no ports to be returned.
-                            if let Ok(pt) = port_type(&d) {
-                                vec.push(SerialPortInfo {
-                                    port_name: String::from(devnode),
-                                    port_type: pt,
-                                });
-                            }
-                        }
-                    }
-                }
+        ///
+        /// udev always operates on the actual system. Custom roots given in `options` are
+        /// enumerated from sysfs instead.
+        pub fn available_ports_with(options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
+            if options.has_default_roots() {
+                UdevEnumerator.available_ports(options)
+            } else {
+                SysfsEnumerator.available_ports(options)
             }
-            Ok(vec)
         }
-    } else if #[cfg(target_os = "linux")] {
-        use std::fs::File;
-        use std::io::Read;
-        use std::path::Path;
 
-        fn is_rfcomm(path: &Path) -> bool {
-            path
-                .file_name()
-                .and_then(|o| o.to_str())
-                .map(|s| s.starts_with("rfcomm"))
-                .unwrap_or(false)
-        }
+        /// Enumerates serial ports with udev
+        ///
+        /// This is the default on Linux with the `libudev` feature enabled. udev always operates
+        /// on the actual system and custom roots in [`EnumerationOptions`] are not supported.
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct UdevEnumerator;
 
-        fn read_file_to_trimmed_string(dir: &Path, file: &str) -> Option<String> {
-            let path = dir.join(file);
-            let mut s = String::new();
-            File::open(path).ok()?.read_to_string(&mut s).ok()?;
-            Some(s.trim().to_owned())
-        }
+        impl PortEnumerator for UdevEnumerator {
+            fn available_ports(&self, options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
+                if !options.has_default_roots() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Enumerating ports with udev does not support custom roots",
+                    ));
+                }
 
-        fn read_file_to_u16(dir: &Path, file: &str) -> Option<u16> {
-            u16::from_str_radix(&read_file_to_trimmed_string(dir, file)?, 16).ok()
+                let mut vec = Vec::new();
+                let aliases = scan_aliases(&options.dev_root);
+                if let Ok(context) = libudev::Context::new() {
+                    let mut enumerator = libudev::Enumerator::new(&context)?;
+                    enumerator.match_subsystem("tty")?;
+                    let devices = enumerator.scan_devices()?;
+                    for d in devices {
+                        if let Some(info) = udev_port_info(&d, options, &aliases) {
+                            vec.push(info);
+                        }
+                    }
+                }
+                if options.include_pseudo_terminals {
+                    vec.extend(pseudo_terminals(&options.dev_root));
+                }
+                Ok(vec)
+            }
         }
 
-        #[cfg(feature = "usbportinfo-interface")]
-        fn read_file_to_u8(dir: &Path, file: &str) -> Option<u8> {
-            u8::from_str_radix(&read_file_to_trimmed_string(dir, file)?, 16).ok()
-        }
+        /// Determines the information about the TTY device `d`. Returns `None` if this is not a
+        /// serial port.
+        fn udev_port_info(
+            d: &libudev::Device,
+            options: &EnumerationOptions,
+            aliases: &HashMap<PathBuf, Vec<String>>,
+        ) -> Option<SerialPortInfo> {
+            let devnode = d.devnode().and_then(|o| o.to_str())?;
+            let parent = d.parent();
+            if parent.is_none() && !is_rfcomm(d) && !is_usb_gadget(d) {
+                return None;
+            }
 
-        fn read_port_type(path: &Path) -> Option<SerialPortType> {
-            let path = path
-                .canonicalize()
-                .ok()?;
-            let subsystem = path.join("subsystem").canonicalize().ok()?;
-            let subsystem = subsystem.file_name()?.to_string_lossy();
-
-            match subsystem.as_ref() {
-                // Broadcom SoC UARTs (of Raspberry Pi devices).
-                "amba" => Some(SerialPortType::Unknown),
-                "pci" => Some(SerialPortType::PciPort),
-                "pnp" => Some(SerialPortType::Unknown),
-                "usb" => usb_port_type(&path),
-                "usb-serial" => usb_port_type(path.parent()?),
-                _ => None,
+            // Stop bubbling up port_type errors here so problematic ports are just skipped
+            // instead of causing no ports to be returned.
+            let pt = port_type(d).ok()?;
+            let type_attribute = udev_attribute_as_string(d, "type");
+            if skip_phantom_port(options, &pt, type_attribute.as_deref()) {
+                return None;
             }
-        }
+            #[cfg(any(feature = "usbportinfo-details", feature = "usbportinfo-interface"))]
+            let pt = match pt {
+                SerialPortType::UsbPort(mut info) => {
+                    udev_add_usb_details(d, &mut info);
+                    SerialPortType::UsbPort(info)
+                }
+                other => other,
+            };
+            #[cfg(not(feature = "serialportinfo-details"))]
+            let _ = aliases;
 
-        fn usb_port_type(interface_path: &Path) -> Option<SerialPortType> {
-            let info = read_usb_port_info(interface_path)?;
-            Some(SerialPortType::UsbPort(info))
+            Some(SerialPortInfo {
+                port_name: String::from(devnode),
+                port_type: pt,
+                #[cfg(feature = "serialportinfo-details")]
+                aliases: aliases_of(aliases, Path::new(devnode)),
+                #[cfg(feature = "serialportinfo-details")]
+                driver: udev_hardware_parent(d)
+                    .and_then(|p| p.driver().map(|driver| driver.to_string_lossy().into_owned())),
+                #[cfg(feature = "serialportinfo-details")]
+                sysfs_path: d
+                    .syspath()
+                    .map(|path| path.to_string_lossy().into_owned()),
+            })
         }
 
-        fn read_usb_port_info(interface_path: &Path) -> Option<UsbPortInfo> {
-            let device_path = interface_path.parent()?;
+        /// Looks up the information about the TTY device `name` with udev. Custom roots given in
+        /// `options` are looked up in sysfs instead.
+        fn lookup_port_info(
+            name: &OsStr,
+            options: &EnumerationOptions,
+            aliases: &HashMap<PathBuf, Vec<String>>,
+        ) -> Option<SerialPortInfo> {
+            let class_path = options.sysfs_root.join("class/tty").join(name);
+            if !options.has_default_roots() {
+                return sysfs::port_info(&class_path, options, aliases);
+            }
 
-            let vid = read_file_to_u16(&device_path, "idVendor")?;
-            let pid = read_file_to_u16(&device_path, "idProduct")?;
-            #[cfg(feature = "usbportinfo-interface")]
-            let interface = read_file_to_u8(&interface_path, &"bInterfaceNumber");
-            let serial_number = read_file_to_trimmed_string(&device_path, &"serial");
-            let product = read_file_to_trimmed_string(&device_path, &"product");
-            let manufacturer = read_file_to_trimmed_string(&device_path, &"manufacturer");
-
-            Some(UsbPortInfo {
-                vid,
-                pid,
-                serial_number,
-                manufacturer,
-                product,
-                #[cfg(feature = "usbportinfo-interface")]
-                interface,
-            })
+            let context = libudev::Context::new().ok()?;
+            let d = libudev::Device::from_syspath(&context, &class_path.canonicalize().ok()?).ok()?;
+            udev_port_info(&d, options, aliases)
         }
-
+    } else if #[cfg(target_os = "linux")] {
         /// Scans `/sys/class/tty` for serial devices (on Linux systems without libudev).
-        pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
-            let mut vec = Vec::new();
-            let sys_path = Path::new("/sys/class/tty/");
-            let dev_path = Path::new("/dev");
-            for path in sys_path.read_dir().expect("/sys/class/tty/ doesn't exist on this system") {
-                let raw_path = path?.path().clone();
-                let mut path = raw_path.clone();
-
-                let port_type = if is_rfcomm(&raw_path) {
-                    SerialPortType::BluetoothPort
-                } else {
-                    path.push("device");
-                    if !path.is_dir() {
-                        continue;
-                    }
-
-                    // Determine port type and proceed, if it's a known.
-                    //
-                    // TODO: Switch to a likely more readable let-else statement when our MSRV supports
-                    // it.
-                    let port_type = read_port_type(&path);
-                    if let Some(port_type) = port_type {
-                        port_type
-                    } else {
-                        continue;
-                    }
-                };
-
-                // Generate the device file path `/dev/DEVICE` from the TTY class path
-                // `/sys/class/tty/DEVICE` and emit a serial device if this path exists. There are
-                // no further checks (yet) due to `Path::is_file` reports only regular files.
-                //
-                // See https://github.com/serialport/serialport-rs/issues/66 for details.
-                if let Some(file_name) = raw_path.file_name() {
-                    let device_file = dev_path.join(file_name);
-                    if !device_file.exists() {
-                        continue;
-                    }
+        pub fn available_ports_with(options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
+            SysfsEnumerator.available_ports(options)
+        }
 
-                    vec.push(SerialPortInfo {
-                        port_name: device_file.to_string_lossy().to_string(),
-                        port_type,
-                    });
-                }
-            }
-            Ok(vec)
+        /// Looks up the information about the TTY device `name` in sysfs.
+        fn lookup_port_info(
+            name: &std::ffi::OsStr,
+            options: &EnumerationOptions,
+            aliases: &HashMap<PathBuf, Vec<String>>,
+        ) -> Option<SerialPortInfo> {
+            let class_path = options.sysfs_root.join("class/tty").join(name);
+            sysfs::port_info(&class_path, options, aliases)
         }
     } else if #[cfg(target_os = "freebsd")] {
-        use std::path::Path;
-
         /// Scans the system for serial ports and returns a list of them.
         /// The `SerialPortInfo` struct contains the name of the port
         /// which can be used for opening it.
-        pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
+        pub fn available_ports_with(options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
             let mut vec = Vec::new();
-            let dev_path = Path::new("/dev/");
-            for path in dev_path.read_dir()? {
+            for path in options.dev_root.read_dir()? {
                 let path = path?;
                 let filename = path.file_name();
                 let filename_string = filename.to_string_lossy();
@@ -678,6 +1223,12 @@ cfg_if! {
                         vec.push(SerialPortInfo {
                             port_name: path.path().to_string_lossy().to_string(),
                             port_type: SerialPortType::Unknown,
+                            #[cfg(feature = "serialportinfo-details")]
+                            aliases: Vec::new(),
+                            #[cfg(feature = "serialportinfo-details")]
+                            driver: None,
+                            #[cfg(feature = "serialportinfo-details")]
+                            sysfs_path: None,
                         });
                     }
                 }
@@ -686,7 +1237,7 @@ cfg_if! {
         }
     } else {
         /// Enumerating serial ports on this platform is not supported
-        pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
+        pub fn available_ports_with(_options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
             Err(Error::new(
                 ErrorKind::Unknown,
                 "Not implemented for this OS",
@@ -748,3 +1299,388 @@ mod tests {
         assert_eq!(info.interface, Some(0xdc));
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod linux_tests {
+    use super::*;
+    use crate::tests::fs::TempDir;
+    use crate::UsbPortInfo;
+
+    #[test]
+    #[cfg(feature = "serialportinfo-details")]
+    fn scan_aliases_collects_persistent_links() {
+        let dev = TempDir::new("dev");
+        let tty_usb0 = dev.file("ttyUSB0", "");
+        dev.file("ttyAMA0", "");
+        dev.symlink(
+            "serial/by-id/usb-FTDI_FT232R_USB_UART_A50285BI-if00-port0",
+            "../../ttyUSB0",
+        );
+        dev.symlink(
+            "serial/by-path/pci-0000:00:14.0-usb-0:2:1.0-port0",
+            "../../ttyUSB0",
+        );
+        dev.symlink("serial0", "ttyAMA0");
+        dev.symlink("stdin", "/proc/self/fd/0");
+
+        let aliases = scan_aliases(dev.path());
+
+        let dev_path = dev.path().to_string_lossy();
+        assert_eq!(
+            aliases_of(&aliases, &tty_usb0),
+            vec![
+                format!(
+                    "{}/serial/by-id/usb-FTDI_FT232R_USB_UART_A50285BI-if00-port0",
+                    dev_path
+                ),
+                format!(
+                    "{}/serial/by-path/pci-0000:00:14.0-usb-0:2:1.0-port0",
+                    dev_path
+                ),
+            ]
+        );
+        assert_eq!(
+            aliases_of(&aliases, &dev.path().join("ttyAMA0")),
+            vec![format!("{}/serial0", dev_path)]
+        );
+        assert!(aliases_of(&aliases, &dev.path().join("ttyS0")).is_empty());
+    }
+
+    /// Fabricates the sysfs device directory `device` belonging to `subsystem` (if not already
+    /// present) and registers `name` as TTY class device below it.
+    fn add_tty(sys: &TempDir, name: &str, device: &str, subsystem: &str) -> PathBuf {
+        let device_dir = sys.dir(device);
+        let subsystem_link = format!("{}/subsystem", device);
+        if !sys.path().join(&subsystem_link).exists() {
+            let subsystem_dir = sys.dir(&format!("bus/{}", subsystem));
+            sys.symlink(&subsystem_link, subsystem_dir);
+        }
+        let class_dir = sys.dir(&format!("{}/tty/{}", device, name));
+        sys.symlink(&format!("{}/tty/{}/device", device, name), device_dir);
+        sys.symlink(&format!("class/tty/{}", name), &class_dir);
+        class_dir
+    }
+
+    /// Binds the fabricated sysfs device directory `device` to `driver`.
+    fn add_driver(sys: &TempDir, device: &str, subsystem: &str, driver: &str) {
+        let driver_dir = sys.dir(&format!("bus/{}/drivers/{}", subsystem, driver));
+        sys.symlink(&format!("{}/driver", device), driver_dir);
+    }
+
+    fn add_usb_device(sys: &TempDir, device: &str, vid: &str, pid: &str, serial: Option<&str>) {
+        sys.file(&format!("{}/idVendor", device), vid);
+        sys.file(&format!("{}/idProduct", device), pid);
+        sys.file(&format!("{}/manufacturer", device), "Acme\n");
+        sys.file(&format!("{}/product", device), "Serial Adapter\n");
+        if let Some(serial) = serial {
+            sys.file(&format!("{}/serial", device), serial);
+        }
+    }
+
+    // The struct update only has an effect with features adding fields to `UsbPortInfo`.
+    #[allow(clippy::needless_update)]
+    #[cfg_attr(not(feature = "usbportinfo-details"), allow(unused_variables))]
+    fn usb_port_info(
+        vid: u16,
+        pid: u16,
+        serial_number: Option<&str>,
+        port_path: &str,
+    ) -> SerialPortType {
+        SerialPortType::UsbPort(UsbPortInfo {
+            vid,
+            pid,
+            serial_number: serial_number.map(String::from),
+            manufacturer: Some("Acme".to_string()),
+            product: Some("Serial Adapter".to_string()),
+            #[cfg(feature = "usbportinfo-interface")]
+            interface: Some(0),
+            #[cfg(feature = "usbportinfo-details")]
+            port_path: Some(port_path.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn sysfs_classifies_fabricated_ports() {
+        let sys = TempDir::new("sys");
+        let dev = TempDir::new("dev");
+
+        // A USB serial converter (like FTDI) with a usb-serial device below its interface.
+        let usb = "devices/pci0000:00/0000:00:14.0/usb1/1-2";
+        add_usb_device(&sys, usb, "0403\n", "6001\n", Some("A50285BI\n"));
+        sys.file(&format!("{}/1-2:1.0/bInterfaceNumber", usb), "00\n");
+        add_tty(
+            &sys,
+            "ttyUSB0",
+            &format!("{}/1-2:1.0/ttyUSB0", usb),
+            "usb-serial",
+        );
+
+        // A CDC ACM device with the TTY directly below its interface.
+        let acm = "devices/pci0000:00/0000:00:14.0/usb1/1-3";
+        add_usb_device(&sys, acm, "2341\n", "0043\n", None);
+        sys.file(&format!("{}/1-3:1.0/bInterfaceNumber", acm), "00\n");
+        add_tty(&sys, "ttyACM0", &format!("{}/1-3:1.0", acm), "usb");
+
+        add_tty(&sys, "ttyS4", "devices/pci0000:00/0000:00:16.3", "pci");
+        add_tty(&sys, "ttyS0", "devices/pnp0/00:01", "pnp");
+        add_driver(&sys, "devices/pnp0/00:01", "pnp", "serial");
+        let amba = "devices/platform/soc/fe201000.serial";
+        add_tty(&sys, "ttyAMA0", amba, "amba");
+        add_driver(&sys, amba, "amba", "uart-pl011");
+        // Since Linux 6.5, there are port and controller devices between the TTY and the actual
+        // hardware.
+        let pnp = "devices/pnp0/00:00";
+        add_tty(
+            &sys,
+            "ttyS1",
+            &format!("{}/00:00:0/00:00:0.0", pnp),
+            "serial-base",
+        );
+        sys.symlink(
+            &format!("{}/00:00:0/subsystem", pnp),
+            sys.path().join("bus/serial-base"),
+        );
+        sys.symlink(&format!("{}/subsystem", pnp), sys.dir("bus/pnp"));
+        add_driver(&sys, pnp, "pnp", "serial");
+        // Ports of unknown subsystems and ports without device file get skipped.
+        add_tty(&sys, "ttyX0", "devices/serio0", "serio");
+        add_tty(&sys, "ttyS5", "devices/pci0000:00/0000:00:16.4", "pci");
+        // Bluetooth, USB gadget and virtual terminals have no device directory.
+        for name in ["rfcomm0", "ttyGS0", "tty0"] {
+            sys.symlink(
+                &format!("class/tty/{}", name),
+                sys.dir(&format!("devices/virtual/tty/{}", name)),
+            );
+        }
+
+        for name in [
+            "ttyUSB0", "ttyACM0", "ttyS4", "ttyS0", "ttyS1", "ttyAMA0", "ttyX0",
+        ] {
+            dev.file(name, "");
+        }
+        dev.file("rfcomm0", "");
+        dev.file("ttyGS0", "");
+        dev.file("tty0", "");
+        dev.symlink("serial0", "ttyAMA0");
+        dev.file("pts/ptmx", "");
+        dev.file("pts/0", "");
+
+        let options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            dev_root: dev.path().to_path_buf(),
+            include_pseudo_terminals: true,
+            ..EnumerationOptions::default()
+        };
+        let mut ports = crate::available_ports_with(&options).unwrap();
+        ports.sort_by(|a, b| a.port_name.cmp(&b.port_name));
+        // The sysfs paths are checked in `sysfs_reports_port_details`.
+        #[cfg(feature = "serialportinfo-details")]
+        for port in ports.iter_mut() {
+            port.sysfs_path = None;
+        }
+
+        let dev_path = dev.path().to_string_lossy();
+        let port = |name: &str, port_type: SerialPortType| SerialPortInfo {
+            port_name: format!("{}/{}", dev_path, name),
+            port_type,
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: Vec::new(),
+            #[cfg(feature = "serialportinfo-details")]
+            driver: None,
+            #[cfg(feature = "serialportinfo-details")]
+            sysfs_path: None,
+        };
+        let platform_port = |name: &str, driver: &str| SerialPortInfo {
+            #[cfg(feature = "serialportinfo-details")]
+            driver: Some(driver.to_string()),
+            ..port(name, platform_port_type(Some(driver.to_string())))
+        };
+        let amba = SerialPortInfo {
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: vec![format!("{}/serial0", dev_path)],
+            ..platform_port("ttyAMA0", "uart-pl011")
+        };
+        assert_eq!(
+            ports,
+            vec![
+                port("pts/0", pseudo_terminal_type()),
+                port("rfcomm0", SerialPortType::BluetoothPort),
+                port("ttyACM0", usb_port_info(0x2341, 0x0043, None, "1-3")),
+                amba,
+                port("ttyGS0", usb_gadget_type()),
+                platform_port("ttyS0", "serial"),
+                platform_port("ttyS1", "serial"),
+                port("ttyS4", SerialPortType::PciPort),
+                port(
+                    "ttyUSB0",
+                    usb_port_info(0x0403, 0x6001, Some("A50285BI"), "1-2")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serialportinfo-details", feature = "usbportinfo-details"))]
+    fn sysfs_reports_port_details() {
+        let sys = TempDir::new("sys");
+        let dev = TempDir::new("dev");
+
+        // A quad port FTDI adapter behind port 3 of a hub at port 2 of bus 1 which provides four
+        // otherwise identical ports.
+        let usb = "devices/pci0000:00/0000:00:14.0/usb1/1-2/1-2.3";
+        add_usb_device(&sys, usb, "0403\n", "6011\n", None);
+        sys.file(&format!("{}/bcdDevice", usb), "0800\n");
+        sys.file(&format!("{}/busnum", usb), "1\n");
+        sys.file(&format!("{}/devnum", usb), "7\n");
+        let mut class_dirs = Vec::new();
+        for i in 0..4 {
+            let interface = format!("{}/1-2.3:1.{}", usb, i);
+            sys.file(
+                &format!("{}/bInterfaceNumber", interface),
+                &format!("0{}\n", i),
+            );
+            sys.file(
+                &format!("{}/interface", interface),
+                &format!("Quad RS232-HS {}\n", ["A", "B", "C", "D"][i]),
+            );
+            let device = format!("{}/ttyUSB{}", interface, i);
+            let name = format!("ttyUSB{}", i);
+            class_dirs.push(add_tty(&sys, &name, &device, "usb-serial"));
+            add_driver(&sys, &device, "usb-serial", "ftdi_sio");
+            dev.file(&name, "");
+        }
+
+        let options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            dev_root: dev.path().to_path_buf(),
+            ..EnumerationOptions::default()
+        };
+        let mut ports = crate::available_ports_with(&options).unwrap();
+        ports.sort_by(|a, b| a.port_name.cmp(&b.port_name));
+
+        assert_eq!(ports.len(), 4);
+        for (i, port) in ports.iter().enumerate() {
+            assert_eq!(port.driver.as_deref(), Some("ftdi_sio"));
+            let class_dir = class_dirs[i].canonicalize().unwrap();
+            assert_eq!(
+                port.sysfs_path.as_deref(),
+                Some(class_dir.to_string_lossy().as_ref())
+            );
+
+            let info = match port.port_type {
+                SerialPortType::UsbPort(ref info) => info,
+                _ => panic!("{} is not a USB port", port.port_name),
+            };
+            assert_eq!(info.bus_number, Some(1));
+            assert_eq!(info.device_address, Some(7));
+            assert_eq!(info.port_path.as_deref(), Some("1-2.3"));
+            assert_eq!(info.bcd_device, Some(0x0800));
+            #[cfg(feature = "usbportinfo-interface")]
+            assert_eq!(info.interface, Some(i as u8));
+        }
+        assert_eq!(ports[1].description(), "Serial Adapter - Quad RS232-HS B");
+    }
+
+    #[test]
+    fn sysfs_skips_phantom_ports() {
+        let sys = TempDir::new("sys");
+        let dev = TempDir::new("dev");
+
+        // The legacy ports of the 8250 driver get registered regardless of the hardware present.
+        // Only the ones with a detected UART (16550A) are real.
+        let uart_types = [("ttyS0", "4\n"), ("ttyS1", "0\n"), ("ttyS2", "0\n")];
+        for (name, uart_type) in uart_types.iter() {
+            add_tty(&sys, name, "devices/platform/serial8250", "platform");
+            sys.file(
+                &format!("devices/platform/serial8250/tty/{}/type", name),
+                uart_type,
+            );
+            dev.file(name, "");
+        }
+
+        let mut options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            dev_root: dev.path().to_path_buf(),
+            ..EnumerationOptions::default()
+        };
+        let port_names = |options: &EnumerationOptions| {
+            let mut names: Vec<_> = crate::available_ports_with(options)
+                .unwrap()
+                .into_iter()
+                .map(|port| port.port_name)
+                .collect();
+            names.sort();
+            names
+        };
+        let dev_path = dev.path().to_string_lossy();
+
+        assert_eq!(port_names(&options), vec![format!("{}/ttyS0", dev_path)]);
+
+        options.include_phantom_ports = true;
+        assert_eq!(
+            port_names(&options),
+            vec![
+                format!("{}/ttyS0", dev_path),
+                format!("{}/ttyS1", dev_path),
+                format!("{}/ttyS2", dev_path),
+            ]
+        );
+    }
+
+    #[test]
+    fn port_info_resolves_aliases() {
+        let sys = TempDir::new("sys");
+        let dev = TempDir::new("dev");
+
+        let usb = "devices/pci0000:00/0000:00:14.0/usb1/1-2";
+        add_usb_device(&sys, usb, "0403\n", "6001\n", Some("A50285BI\n"));
+        sys.file(&format!("{}/1-2:1.0/bInterfaceNumber", usb), "00\n");
+        let device = format!("{}/1-2:1.0/ttyUSB3", usb);
+        add_tty(&sys, "ttyUSB3", &device, "usb-serial");
+        sys.symlink("class/tty/tty0", sys.dir("devices/virtual/tty/tty0"));
+        let tty_usb3 = dev.file("ttyUSB3", "");
+        dev.file("tty0", "");
+        dev.file("pts/3", "");
+        let by_id = dev.symlink(
+            "serial/by-id/usb-Acme_Serial_Adapter_A50285BI-if00-port0",
+            "../../ttyUSB3",
+        );
+
+        let options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            dev_root: dev.path().to_path_buf(),
+            ..EnumerationOptions::default()
+        };
+        let expected = crate::available_ports_with(&options).unwrap();
+        assert_eq!(expected.len(), 1);
+        assert_eq!(port_info_with(&tty_usb3, &options).unwrap(), expected[0]);
+        assert_eq!(port_info_with(&by_id, &options).unwrap(), expected[0]);
+
+        let pts = port_info_with(&dev.path().join("pts/3"), &options).unwrap();
+        assert_eq!(pts.port_type, pseudo_terminal_type());
+
+        let error = port_info_with(&dev.path().join("tty0"), &options).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::NoDevice);
+        let error = port_info_with(&dev.path().join("ttyUSB4"), &options).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            crate::ErrorKind::Io(std::io::ErrorKind::NotFound)
+        );
+    }
+
+    #[test]
+    fn sysfs_missing_tty_class_is_an_error() {
+        let sys = TempDir::new("sys");
+        let options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            ..EnumerationOptions::default()
+        };
+        let error = crate::available_ports_with(&options).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            crate::ErrorKind::Io(std::io::ErrorKind::NotFound)
+        );
+        assert!(error.description.contains("class/tty"));
+    }
+}
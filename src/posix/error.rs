@@ -15,25 +15,57 @@ impl From<libudev::Error> for Error {
     }
 }
 
+/// Determines the kind of error for the error code `errno`.
+#[cfg(feature = "error-details")]
+pub(crate) fn errno_kind(errno: nix::errno::Errno) -> ErrorKind {
+    use io::ErrorKind as IO;
+    use nix::errno::Errno as E;
+    use ErrorKind as K;
+    match errno {
+        E::ETIMEDOUT => K::TimedOut,
+        E::EACCES | E::EPERM => K::PermissionDenied,
+        E::EBUSY => K::Busy,
+        E::ENODEV | E::ENXIO => K::NoDevice,
+        E::ENOTTY | E::ENOSYS | E::EOPNOTSUPP => K::Unsupported,
+        E::ECONNABORTED => K::Io(IO::ConnectionAborted),
+        E::ECONNRESET => K::Io(IO::ConnectionReset),
+        E::ECONNREFUSED => K::Io(IO::ConnectionRefused),
+        E::ENOTCONN => K::Io(IO::NotConnected),
+        E::EADDRINUSE => K::Io(IO::AddrInUse),
+        E::EADDRNOTAVAIL => K::Io(IO::AddrNotAvailable),
+        E::EAGAIN => K::Io(IO::WouldBlock),
+        E::EINTR => K::Io(IO::Interrupted),
+        E::ENOENT => K::Io(IO::NotFound),
+        _ => K::Unknown,
+    }
+}
+
+/// Determines the kind of error for the error code `errno`.
+#[cfg(not(feature = "error-details"))]
+pub(crate) fn errno_kind(errno: nix::errno::Errno) -> ErrorKind {
+    use io::ErrorKind as IO;
+    use nix::errno::Errno as E;
+    use ErrorKind as K;
+    match errno {
+        E::ETIMEDOUT => K::Io(IO::TimedOut),
+        E::ECONNABORTED => K::Io(IO::ConnectionAborted),
+        E::ECONNRESET => K::Io(IO::ConnectionReset),
+        E::ECONNREFUSED => K::Io(IO::ConnectionRefused),
+        E::ENOTCONN => K::Io(IO::NotConnected),
+        E::EADDRINUSE => K::Io(IO::AddrInUse),
+        E::EADDRNOTAVAIL => K::Io(IO::AddrNotAvailable),
+        E::EAGAIN => K::Io(IO::WouldBlock),
+        E::EINTR => K::Io(IO::Interrupted),
+        E::EACCES => K::Io(IO::PermissionDenied),
+        E::ENOENT => K::Io(IO::NotFound),
+        _ => K::Unknown,
+    }
+}
+
 impl From<nix::Error> for Error {
     fn from(e: nix::Error) -> Error {
-        use io::ErrorKind as IO;
-        use nix::errno::Errno as E;
-        use ErrorKind as K;
-        let kind = match e {
-            E::ETIMEDOUT => K::Io(IO::TimedOut),
-            E::ECONNABORTED => K::Io(IO::ConnectionAborted),
-            E::ECONNRESET => K::Io(IO::ConnectionReset),
-            E::ECONNREFUSED => K::Io(IO::ConnectionRefused),
-            E::ENOTCONN => K::Io(IO::NotConnected),
-            E::EADDRINUSE => K::Io(IO::AddrInUse),
-            E::EADDRNOTAVAIL => K::Io(IO::AddrNotAvailable),
-            E::EAGAIN => K::Io(IO::WouldBlock),
-            E::EINTR => K::Io(IO::Interrupted),
-            E::EACCES => K::Io(IO::PermissionDenied),
-            E::ENOENT => K::Io(IO::NotFound),
-            _ => K::Unknown,
-        };
-        Error::new(kind, e.desc())
+        Error::new(errno_kind(e), e.desc())
+            .with_raw_os_error(e as i32)
+            .with_source(e)
     }
 }
@@ -19,6 +19,15 @@ mod raw {
     #[cfg(any(target_os = "android", target_os = "linux"))]
     ioctl_read_bad!(fionread, libc::FIONREAD, libc::c_int);
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    ioctl_read_bad!(tiocgserial, libc::TIOCGSERIAL, super::SerialStruct);
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    ioctl_write_ptr_bad!(tiocsserial, libc::TIOCSSERIAL, super::SerialStruct);
+
+    #[cfg(target_os = "linux")]
+    ioctl_read_bad!(tiocsergetlsr, libc::TIOCSERGETLSR, libc::c_uint);
+
     // See: /usr/include/sys/filio.h
     #[cfg(any(
         target_os = "dragonfly",
@@ -104,6 +113,52 @@ bitflags! {
     }
 }
 
+/// Mirrors `struct serial_struct` from `linux/serial.h`
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SerialStruct {
+    pub type_: libc::c_int,
+    pub line: libc::c_int,
+    pub port: libc::c_uint,
+    pub irq: libc::c_int,
+    pub flags: libc::c_int,
+    pub xmit_fifo_size: libc::c_int,
+    pub custom_divisor: libc::c_int,
+    pub baud_base: libc::c_int,
+    pub close_delay: libc::c_ushort,
+    pub io_type: libc::c_char,
+    pub reserved_char: [libc::c_char; 1],
+    pub hub6: libc::c_int,
+    pub closing_wait: libc::c_ushort,
+    pub closing_wait2: libc::c_ushort,
+    pub iomem_base: *mut libc::c_uchar,
+    pub iomem_reg_shift: libc::c_ushort,
+    pub port_high: libc::c_uint,
+    pub iomap_base: libc::c_ulong,
+}
+
+/// UART type of serial ports without actual hardware (`PORT_UNKNOWN` from `linux/serial.h`)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const PORT_UNKNOWN: libc::c_int = 0;
+
+/// Flag in `SerialStruct::flags` for minimizing receive latency (`ASYNC_LOW_LATENCY` from
+/// `linux/tty_flags.h`)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const ASYNC_LOW_LATENCY: libc::c_int = 1 << 13;
+
+/// Mask and value in `SerialStruct::flags` for using `SerialStruct::custom_divisor` instead of
+/// 38400 baud (`ASYNC_SPD_MASK` and `ASYNC_SPD_CUST` from `linux/tty_flags.h`)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const ASYNC_SPD_MASK: libc::c_int = 0x1030;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const ASYNC_SPD_CUST: libc::c_int = 0x0030;
+
+/// Line status bit of `TIOCSERGETLSR` for an empty transmitter (`TIOCSER_TEMT` from
+/// `asm-generic/ioctls.h`)
+#[cfg(target_os = "linux")]
+pub const TIOCSER_TEMT: libc::c_uint = 0x01;
+
 pub fn tiocexcl(fd: RawFd) -> Result<()> {
     unsafe { raw::tiocexcl(fd) }
         .map(|_| ())
@@ -142,6 +197,30 @@ pub fn fionread(fd: RawFd) -> Result<u32> {
         .map_err(|e| e.into())
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn tiocgserial(fd: RawFd) -> Result<SerialStruct> {
+    let mut serial = std::mem::MaybeUninit::uninit();
+    match unsafe { raw::tiocgserial(fd, serial.as_mut_ptr()) } {
+        Ok(_) => unsafe { Ok(serial.assume_init()) },
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn tiocsserial(fd: RawFd, serial: &SerialStruct) -> Result<()> {
+    unsafe { raw::tiocsserial(fd, serial) }
+        .map(|_| ())
+        .map_err(|e| e.into())
+}
+
+#[cfg(target_os = "linux")]
+pub fn tiocsergetlsr(fd: RawFd) -> Result<libc::c_uint> {
+    let mut status: libc::c_uint = 0;
+    unsafe { raw::tiocsergetlsr(fd, &mut status) }
+        .map(|_| status)
+        .map_err(|e| e.into())
+}
+
 pub fn tiocoutq(fd: RawFd) -> Result<u32> {
     let mut retval: libc::c_int = 0;
     unsafe { raw::tiocoutq(fd, &mut retval) }
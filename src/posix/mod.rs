@@ -1,5 +1,8 @@
 pub use self::enumerate::*;
+#[cfg(feature = "error-details")]
+pub(crate) use self::error::errno_kind;
 pub use self::tty::*;
+pub(crate) use self::watch::HotplugSignal;
 
 mod enumerate;
 mod error;
@@ -7,3 +10,4 @@ mod ioctl;
 mod poll;
 mod termios;
 mod tty;
+mod watch;
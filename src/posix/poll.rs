@@ -40,9 +40,17 @@ fn wait_fd(fd: RawFd, events: PollFlags, timeout: Duration) -> io::Result<()> {
 
     // Check the result of ppoll() by looking at the revents field
     match fd.revents() {
-        Some(e) if e == events => return Ok(()),
-        // If there was a hangout or invalid request
-        Some(e) if e.contains(PollFlags::POLLHUP) || e.contains(PollFlags::POLLNVAL) => {
+        // Data which was received before a hang-up can still be read.
+        Some(e) if e.contains(events) => return Ok(()),
+        // The device has been hung up (like a USB serial adapter being unplugged)
+        Some(e) if e.intersects(PollFlags::POLLHUP | PollFlags::POLLERR) => {
+            return Err(io::Error::from(crate::Error::new(
+                crate::ErrorKind::DISCONNECTED,
+                DISCONNECTED,
+            )));
+        }
+        // If there was an invalid request
+        Some(e) if e.contains(PollFlags::POLLNVAL) => {
             return Err(io::Error::new(io::ErrorKind::BrokenPipe, EPIPE.desc()));
         }
         Some(_) | None => (),
@@ -51,6 +59,18 @@ fn wait_fd(fd: RawFd, events: PollFlags, timeout: Duration) -> io::Result<()> {
     Err(io::Error::new(io::ErrorKind::Other, EIO.desc()))
 }
 
+/// Description of errors due to a hung up device
+pub const DISCONNECTED: &str = "The device has been disconnected";
+
+/// Checks whether the device has been hung up without waiting.
+pub fn is_hung_up(fd: RawFd) -> io::Result<bool> {
+    let mut fd = PollFd::new(fd, PollFlags::empty());
+    poll_clamped(&mut fd, Duration::ZERO).map_err(|e| io::Error::from(crate::Error::from(e)))?;
+    Ok(fd.revents().map_or(false, |e| {
+        e.intersects(PollFlags::POLLHUP | PollFlags::POLLERR)
+    }))
+}
+
 /// Poll with a duration clamped to the maximum value representable by the `TimeSpec` used by
 /// `ppoll`.
 #[cfg(target_os = "linux")]
@@ -195,6 +195,22 @@ pub(crate) fn set_data_bits(termios: &mut Termios, data_bits: DataBits) {
     termios.c_cflag |= size;
 }
 
+pub(crate) fn set_hangup_on_close(termios: &mut Termios, hangup: bool) {
+    if hangup {
+        termios.c_cflag |= libc::HUPCL;
+    } else {
+        termios.c_cflag &= !libc::HUPCL;
+    }
+}
+
+pub(crate) fn set_carrier_detect(termios: &mut Termios, honor: bool) {
+    if honor {
+        termios.c_cflag &= !libc::CLOCAL;
+    } else {
+        termios.c_cflag |= libc::CLOCAL;
+    }
+}
+
 pub(crate) fn set_stop_bits(termios: &mut Termios, stop_bits: StopBits) {
     match stop_bits {
         StopBits::One => termios.c_cflag &= !libc::CSTOPB,
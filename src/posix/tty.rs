@@ -1,24 +1,29 @@
+use std::fs::File;
 use std::mem::MaybeUninit;
 use std::os::unix::prelude::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
-use std::{io, mem};
+use std::{fmt, io, mem};
 
-use nix::fcntl::{fcntl, OFlag};
+use nix::fcntl::{fcntl, FcntlArg, OFlag};
 use nix::{libc, unistd};
 
 use crate::posix::ioctl::{self, SerialLines};
 use crate::posix::termios;
+use crate::strict::Mismatches;
 use crate::{
     ClearBuffer, DataBits, Error, ErrorKind, FlowControl, Parity, Result, SerialPort,
     SerialPortBuilder, StopBits,
 };
 
-/// Convenience method for removing exclusive access from
-/// a fd and closing it.
-fn close(fd: RawFd) {
+/// Convenience method for closing a fd and removing exclusive
+/// access from it if it has been claimed.
+fn close(fd: RawFd, exclusive: bool) {
     // remove exclusive access
-    let _ = ioctl::tiocnxcl(fd);
+    if exclusive {
+        let _ = ioctl::tiocnxcl(fd);
+    }
 
     // On Linux and BSD, we don't need to worry about return
     // type as EBADF means the fd was never open or is already closed
@@ -60,13 +65,130 @@ fn close(fd: RawFd) {
 #[derive(Debug)]
 pub struct TTYPort {
     fd: RawFd,
-    timeout: Duration,
+    /// Behind a mutex for changing it through shared references
+    timeout: Mutex<Duration>,
     exclusive: bool,
+    strict: bool,
+    carrier_detect: bool,
     port_name: Option<String>,
+    /// Latency timer attribute of an FTDI adapter and its value before entering low-latency mode
+    /// through this port, which gets restored when dropping it
+    #[cfg(target_os = "linux")]
+    ftdi_latency_timer: Option<(PathBuf, u8)>,
     #[cfg(any(target_os = "ios", target_os = "macos"))]
     baud_rate: u32,
 }
 
+/// Hardware details of a UART as returned by [`TTYPort::serial_info`]
+///
+/// The fields mirror `struct serial_struct` from `linux/serial.h`.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerialInfo {
+    /// Type of the UART (one of the `PORT_*` constants from `linux/serial_core.h`, see
+    /// [`SerialInfo::uart_name`])
+    pub uart_type: u32,
+    /// Line number of the port
+    pub line: u32,
+    /// I/O port address
+    pub port: u64,
+    /// Memory-mapped I/O address
+    pub iomem_base: u64,
+    /// Interrupt line
+    pub irq: u32,
+    /// Size of the transmit FIFO in bytes
+    pub xmit_fifo_size: u32,
+    /// Base baud rate the UART divides down from
+    pub baud_base: u32,
+    /// Divisor for custom baud rates (used with the `ASYNC_SPD_CUST` flag)
+    pub custom_divisor: u32,
+    /// Port flags (the `ASYNC_*` constants from `linux/tty_flags.h`)
+    pub flags: u32,
+    /// Whether the transmitter (holding and shift register) is empty or `None` if the driver
+    /// does not report the line status
+    pub transmitter_empty: Option<bool>,
+}
+
+#[cfg(target_os = "linux")]
+impl SerialInfo {
+    /// Returns the name of the UART type (like `16550A` or `16C950`) or `None` for unknown types
+    pub fn uart_name(&self) -> Option<&'static str> {
+        let name = match self.uart_type {
+            0 => "unknown",
+            1 => "8250",
+            2 => "16450",
+            3 => "16550",
+            4 => "16550A",
+            5 => "Cirrus",
+            6 => "16650",
+            7 => "16650V2",
+            8 => "16750",
+            9 => "Startech",
+            10 => "16C950",
+            11 => "16654",
+            12 => "16850",
+            13 => "RSA",
+            14 => "XScale",
+            15 => "RM9000",
+            16 => "Octeon",
+            17 => "AR7",
+            18 => "U6_16550A",
+            19 => "Tegra",
+            20 => "XR17D15X",
+            21 => "LPC3220",
+            22 => "8250_CIR",
+            23 => "XR17V35X",
+            24 => "TruManage",
+            25 => "Altera 16550 FIFO32",
+            26 => "Altera 16550 FIFO64",
+            27 => "Altera 16550 FIFO128",
+            28 => "RT2880",
+            29 => "16550A_FSL64",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+/// The baud rate a UART actually produces as returned by [`TTYPort::effective_baud_rate`]
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveBaudRate {
+    /// The baud rate set for the port
+    pub requested: u32,
+    /// The nearest baud rate the UART can produce from its base baud rate with an integer divisor
+    pub effective: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl EffectiveBaudRate {
+    /// Computes the baud rate produced by dividing `baud_base` down to `requested` with the
+    /// nearest integer divisor (or `custom_divisor` if given).
+    fn new(requested: u32, baud_base: u32, custom_divisor: Option<u32>) -> Self {
+        let divisor = custom_divisor.unwrap_or_else(|| {
+            let requested = u64::from(requested.max(1));
+            ((u64::from(baud_base) + requested / 2) / requested).max(1) as u32
+        });
+        let effective =
+            ((u64::from(baud_base) + u64::from(divisor) / 2) / u64::from(divisor)) as u32;
+        EffectiveBaudRate {
+            requested,
+            effective,
+        }
+    }
+
+    /// Returns the deviation of the effective from the requested baud rate in percent
+    ///
+    /// This is negative if the effective baud rate is lower than the requested one. Deviations
+    /// of more than about 2 % between both ends of a connection cause framing errors.
+    pub fn error_percent(&self) -> f64 {
+        if self.requested == 0 {
+            return 0.0;
+        }
+        (f64::from(self.effective) - f64::from(self.requested)) * 100.0 / f64::from(self.requested)
+    }
+}
+
 /// Specifies the duration of a transmission break
 #[derive(Clone, Copy, Debug)]
 pub enum BreakDuration {
@@ -79,16 +201,25 @@ pub enum BreakDuration {
 /// Wrapper for RawFd to assure that it's properly closed,
 /// even if the enclosing function exits early.
 ///
-/// This is similar to the (nightly-only) std::os::unix::io::OwnedFd.
-struct OwnedFd(RawFd);
+/// This is similar to the (nightly-only) std::os::unix::io::OwnedFd. The
+/// flag tells whether exclusive access has been claimed and needs to be
+/// released again.
+struct OwnedFd(RawFd, bool);
 
 impl Drop for OwnedFd {
     fn drop(&mut self) {
-        close(self.0);
+        close(self.0, self.1);
     }
 }
 
 impl OwnedFd {
+    /// Claims exclusive access to the device until dropping the fd.
+    fn claim_exclusive(&mut self) -> Result<()> {
+        ioctl::tiocexcl(self.0)?;
+        self.1 = true;
+        Ok(())
+    }
+
     fn into_raw(self) -> RawFd {
         let fd = self.0;
         mem::forget(self);
@@ -96,13 +227,90 @@ impl OwnedFd {
     }
 }
 
+/// Options for adopting an open file descriptor with [`TTYPort::from_fd_with`]
+///
+/// The defaults set up the device like [`TTYPort::open`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdoptOptions {
+    /// Apply the settings of the builder to the device
+    ///
+    /// Like when opening a port, the settings are checked against the USB adapter of the device
+    /// first (see [`SerialPortBuilder::check_usb_chip`]). Otherwise, the device is used with its
+    /// current settings and only the timeout and strict mode are taken from the builder.
+    pub configure: bool,
+    /// Claim exclusive access to the device (see [`TTYPort::set_exclusive`])
+    pub exclusive: bool,
+    /// Look up the port name through `/proc/self/fd` (Linux only)
+    pub resolve_name: bool,
+}
+
+impl Default for AdoptOptions {
+    fn default() -> Self {
+        AdoptOptions {
+            configure: true,
+            exclusive: true,
+            resolve_name: true,
+        }
+    }
+}
+
+/// Checks whether the device behind `fd` honors data carrier detect (`CLOCAL` is cleared).
+fn honors_carrier_detect(fd: RawFd) -> bool {
+    termios::get_termios(fd).map_or(false, |termios| termios.c_cflag & libc::CLOCAL == 0)
+}
+
+/// Looks up the path of the device behind `fd`.
+fn fd_path(fd: RawFd) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    return std::fs::read_link(format!("/proc/self/fd/{}", fd))
+        .ok()
+        .and_then(|path| path.into_os_string().into_string().ok());
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = fd;
+        None
+    }
+}
+
+/// Converts an error from opening the device at `path`. If the device is busy (like when another
+/// process opened it in exclusive mode), the description names the users of the device.
+fn open_error(path: &Path, e: nix::Error) -> Error {
+    use nix::errno::Errno;
+
+    let mut error = Error::from(e);
+    match e {
+        Errno::EBUSY => {
+            if let Ok(users) = crate::port_users(path) {
+                if !users.is_empty() {
+                    error.description =
+                        format!("{}: {} is {}", error.description, path.display(), users);
+                }
+            }
+        }
+        Errno::EACCES | Errno::EPERM | Errno::ENOENT => {
+            let diagnostics = crate::access::diagnose(path);
+            if e != Errno::ENOENT || diagnostics.dangling_symlink {
+                error.description = format!("{}: {}", error.description, diagnostics);
+                error = error.with_access_diagnostics(diagnostics);
+            }
+        }
+        _ => {}
+    }
+    error
+}
+
 impl TTYPort {
     /// Opens a TTY device as a serial port.
     ///
     /// `path` should be the path to a TTY device, e.g., `/dev/ttyS0`.
     ///
     /// Ports are opened in exclusive mode by default. If this is undesirable
-    /// behavior, use `TTYPort::set_exclusive(false)`.
+    /// behavior, use [`SerialPortBuilder::exclusive`] or
+    /// `TTYPort::set_exclusive(false)`.
+    ///
+    /// With [`SerialPortBuilder::keep_existing_settings`], the settings of the
+    /// device are left untouched.
     ///
     /// If the port settings differ from the default settings, characters received
     /// before the new settings become active may be garbled. To remove those
@@ -111,24 +319,182 @@ impl TTYPort {
     /// ## Errors
     ///
     /// * `NoDevice` if the device could not be opened. This could indicate that
-    ///    the device is already in use.
-    /// * `InvalidInput` if `path` is not a valid device name.
+    ///   the device has no actual hardware.
+    /// * `Busy` (with the feature `error-details`) if the device is in use in
+    ///   exclusive mode. On Linux, the description names the processes using
+    ///   the device (see [`port_users`](crate::port_users)).
+    /// * `PermissionDenied` (with the feature `error-details`) if the current
+    ///   user may not access the device. The description names the owner,
+    ///   group and mode of the device and whether the user is a member of the
+    ///   group (see `Error::access_diagnostics`).
+    /// * `InvalidInput` if `path` is not a valid device name or the USB
+    ///   adapter does not support the settings (see
+    ///   [`SerialPortBuilder::check_usb_chip`]).
+    /// * `Unsupported` (with the feature `error-details`) if the device did
+    ///   not apply the settings in strict mode (see
+    ///   [`SerialPortBuilder::strict`]).
     /// * `Io` for any other error while opening or initializing the device.
     pub fn open(builder: &SerialPortBuilder) -> Result<TTYPort> {
-        use nix::fcntl::FcntlArg::F_SETFL;
-        use nix::libc::{cfmakeraw, tcgetattr, tcsetattr};
+        builder.verify_baud_rate_tolerance()?;
+        if !builder.keep_existing_settings {
+            builder.verify_usb_chip()?;
+        }
 
         let path = Path::new(&builder.path);
-        let fd = OwnedFd(nix::fcntl::open(
-            path,
-            OFlag::O_RDWR | OFlag::O_NOCTTY | OFlag::O_NONBLOCK | OFlag::O_CLOEXEC,
-            nix::sys::stat::Mode::empty(),
-        )?);
+        let mut fd = OwnedFd(
+            nix::fcntl::open(
+                path,
+                OFlag::O_RDWR | OFlag::O_NOCTTY | OFlag::O_NONBLOCK | OFlag::O_CLOEXEC,
+                nix::sys::stat::Mode::empty(),
+            )
+            .map_err(|e| open_error(path, e))?,
+            false,
+        );
 
         // Try to claim exclusive access to the port. This is performed even
         // if the port will later be set as non-exclusive, in order to respect
         // other applications that may have an exclusive port lock.
-        ioctl::tiocexcl(fd.0)?;
+        fd.claim_exclusive()?;
+
+        let port_name = Some(builder.path.clone());
+        let mut port = if builder.keep_existing_settings {
+            fcntl(fd.0, FcntlArg::F_SETFL(OFlag::empty()))?;
+            TTYPort {
+                #[cfg(any(target_os = "ios", target_os = "macos"))]
+                baud_rate: get_termios_speed(fd.0),
+                carrier_detect: honors_carrier_detect(fd.0),
+                fd: fd.into_raw(),
+                timeout: Mutex::new(builder.timeout),
+                exclusive: true,
+                strict: builder.strict,
+                port_name,
+                #[cfg(target_os = "linux")]
+                ftdi_latency_timer: None,
+            }
+        } else {
+            Self::configure(fd, builder, true, port_name)?
+        };
+        if !builder.exclusive {
+            port.set_exclusive(false)?;
+        }
+        Ok(port)
+    }
+
+    /// Adopts an already open file descriptor of a TTY device as a serial port.
+    ///
+    /// This is for file descriptors inherited from a parent process or passed by a service
+    /// manager (like with systemd socket activation). Unlike
+    /// [`from_raw_fd`](FromRawFd::from_raw_fd), the timeout and the settings are taken from
+    /// `builder` and `options` control how the device gets set up. The port name is looked up
+    /// through `/proc/self/fd` on Linux if requested and falls back to the path of `builder`
+    /// (unless it is empty).
+    ///
+    /// ```no_run
+    /// use serialport::{AdoptOptions, TTYPort};
+    ///
+    /// // File descriptor 3 is the first one passed with systemd socket activation.
+    /// let builder = serialport::new("", 115_200);
+    /// let port = unsafe { TTYPort::from_fd_with(3, &builder, AdoptOptions::default()) }
+    ///     .expect("Failed to adopt port");
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// `fd` must be an open file descriptor which is not owned by anything else. The port takes
+    /// ownership of it and closes it when dropped, or right away if adopting the port fails.
+    ///
+    /// ## Errors
+    ///
+    /// * `Busy` if exclusive access was requested and the device is in use in
+    ///   exclusive mode.
+    /// * The errors of [`open`](TTYPort::open) for applying the settings if
+    ///   [`AdoptOptions::configure`] is set. This includes checking the USB
+    ///   adapter of the device (see [`SerialPortBuilder::check_usb_chip`]).
+    pub unsafe fn from_fd_with(
+        fd: RawFd,
+        builder: &SerialPortBuilder,
+        options: AdoptOptions,
+    ) -> Result<TTYPort> {
+        let mut fd = OwnedFd(fd, false);
+        builder.verify_baud_rate_tolerance()?;
+        if options.exclusive {
+            fd.claim_exclusive()?;
+        }
+
+        let port_name = if options.resolve_name {
+            fd_path(fd.0)
+        } else {
+            None
+        }
+        .or_else(|| Some(builder.path.clone()).filter(|path| !path.is_empty()));
+
+        if options.configure {
+            // The adapter is looked up by the name of the adopted device rather than the path of
+            // `builder`, which may be empty or name a different device.
+            let mut checked = builder.clone();
+            if let Some(name) = &port_name {
+                checked.path = name.clone();
+            }
+            checked.verify_usb_chip()?;
+            return Self::configure(fd, builder, options.exclusive, port_name);
+        }
+        Ok(TTYPort {
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            baud_rate: get_termios_speed(fd.0),
+            carrier_detect: honors_carrier_detect(fd.0),
+            fd: fd.into_raw(),
+            timeout: Mutex::new(builder.timeout),
+            exclusive: options.exclusive,
+            strict: builder.strict,
+            port_name,
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
+        })
+    }
+
+    /// Adopts an open TTY device as a serial port, leaving its settings untouched.
+    ///
+    /// The port name is looked up through `/proc/self/fd` on Linux. Like for
+    /// [`from_raw_fd`](FromRawFd::from_raw_fd), the timeout is 100 ms. Exclusive access is
+    /// neither claimed nor released and [`exclusive`](TTYPort::exclusive) reports `false`. Use
+    /// [`from_fd_with`](TTYPort::from_fd_with) for more control.
+    ///
+    /// ```no_run
+    /// use std::fs::OpenOptions;
+    /// use serialport::TTYPort;
+    ///
+    /// let file = OpenOptions::new()
+    ///     .read(true)
+    ///     .write(true)
+    ///     .open("/dev/ttyUSB0")
+    ///     .expect("Failed to open device");
+    /// let port = TTYPort::from_file(file).expect("Failed to adopt port");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This currently does not fail, but might report errors from looking up
+    /// the device in the future.
+    pub fn from_file(file: File) -> Result<TTYPort> {
+        let builder = crate::new("", 0).timeout(Duration::from_millis(100));
+        let options = AdoptOptions {
+            configure: false,
+            exclusive: false,
+            resolve_name: true,
+        };
+        // Safe as the file gives up the ownership of its file descriptor.
+        unsafe { Self::from_fd_with(file.into_raw_fd(), &builder, options) }
+    }
+
+    /// Applies the settings of `builder` to the device behind `fd` and returns it as a port.
+    fn configure(
+        fd: OwnedFd,
+        builder: &SerialPortBuilder,
+        exclusive: bool,
+        port_name: Option<String>,
+    ) -> Result<TTYPort> {
+        use nix::fcntl::FcntlArg::F_SETFL;
+        use nix::libc::{cfmakeraw, tcgetattr, tcsetattr};
 
         let mut termios = MaybeUninit::uninit();
         nix::errno::Errno::result(unsafe { tcgetattr(fd.0, termios.as_mut_ptr()) })?;
@@ -175,6 +541,10 @@ impl TTYPort {
         termios::set_flow_control(&mut termios, builder.flow_control);
         termios::set_data_bits(&mut termios, builder.data_bits);
         termios::set_stop_bits(&mut termios, builder.stop_bits);
+        termios::set_carrier_detect(&mut termios, builder.carrier_detect);
+        if let Some(hangup) = builder.hangup_on_close {
+            termios::set_hangup_on_close(&mut termios, hangup);
+        }
         #[cfg(not(any(target_os = "ios", target_os = "macos")))]
         termios::set_baud_rate(&mut termios, builder.baud_rate)?;
         #[cfg(any(target_os = "ios", target_os = "macos"))]
@@ -183,15 +553,31 @@ impl TTYPort {
         termios::set_termios(fd.0, &termios)?;
 
         // Return the final port object
-        let mut port = TTYPort {
+        let port = TTYPort {
             fd: fd.into_raw(),
-            timeout: builder.timeout,
-            exclusive: true,
-            port_name: Some(builder.path.clone()),
+            timeout: Mutex::new(builder.timeout),
+            exclusive,
+            strict: builder.strict,
+            carrier_detect: builder.carrier_detect,
+            port_name,
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             baud_rate: builder.baud_rate,
         };
 
+        #[cfg(target_os = "linux")]
+        if let Some(tolerance) = builder.baud_rate_tolerance {
+            check_baud_rate_tolerance(
+                port.effective_baud_rate().ok(),
+                tolerance.0,
+                Path::new("/sys"),
+                port.port_name.as_deref().map(Path::new),
+            )?;
+        }
+
+        builder.verify_settings(&port)?;
+
         // Ignore setting DTR for pseudo terminals. This might be indicated by baud_rate == 0, but
         // as this is not always the case, just try on best-effort.
         if builder.baud_rate > 0 {
@@ -233,16 +619,406 @@ impl TTYPort {
         Ok(())
     }
 
-    fn set_pin(&mut self, pin: ioctl::SerialLines, level: bool) -> Result<()> {
+    /// Returns whether the setters of the port verify that the device applied the setting
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether the setters of the port verify that the device applied the setting
+    ///
+    /// In strict mode, [`SerialPort::set_baud_rate`], [`SerialPort::set_data_bits`],
+    /// [`SerialPort::set_parity`], [`SerialPort::set_stop_bits`] and
+    /// [`SerialPort::set_flow_control`] read back the setting after applying it and fail with
+    /// `Unsupported` if the device reports a different value. Ports opened with
+    /// [`SerialPortBuilder::strict`] start out in strict mode.
+    ///
+    /// On macOS and iOS, the baud rate can't be read back from the device and is not verified.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Reads back a setting in strict mode and fails if it differs from the `requested` one
+    fn verify_setting<T: PartialEq + fmt::Display>(
+        &self,
+        setting: &str,
+        requested: T,
+        actual: impl FnOnce(&Self) -> Result<T>,
+    ) -> Result<()> {
+        if !self.strict {
+            return Ok(());
+        }
+        let mut mismatches = Mismatches::default();
+        mismatches.check(setting, requested, actual(self)?);
+        mismatches.into_result()
+    }
+
+    /// Returns whether the modem control lines get hung up when the port is closed
+    ///
+    /// If enabled, the kernel drops DTR and RTS when the last handle to the device gets closed.
+    /// See the man page for termios (`HUPCL`) for more details.
+    ///
+    /// ## Errors
+    ///
+    /// * `Io` for any error while reading the settings of the port.
+    pub fn hangup_on_close(&self) -> Result<bool> {
+        let termios = termios::get_termios(self.fd)?;
+        Ok(termios.c_cflag & libc::HUPCL == libc::HUPCL)
+    }
+
+    /// Sets whether to hang up the modem control lines when the port is closed
+    ///
+    /// Disabling this keeps DTR asserted after closing the port. This allows to close and reopen
+    /// a port without resetting boards which reset on a falling DTR.
+    ///
+    /// ## Errors
+    ///
+    /// * `Io` for any error while applying the setting to the port.
+    pub fn set_hangup_on_close(&mut self, hangup: bool) -> Result<()> {
+        let mut termios = termios::get_termios(self.fd)?;
+        termios::set_hangup_on_close(&mut termios, hangup);
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        return termios::set_termios(self.fd, &termios, self.baud_rate);
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        return termios::set_termios(self.fd, &termios);
+    }
+
+    /// Returns whether the port honors data carrier detect (DCD)
+    ///
+    /// See [`SerialPortBuilder::carrier_detect`].
+    pub fn carrier_detect(&self) -> bool {
+        self.carrier_detect
+    }
+
+    /// Sets whether the port honors data carrier detect (DCD)
+    ///
+    /// When honoring carrier detect, reading and writing fail with `HangUp` once the carrier
+    /// drops. See the man page for termios (`CLOCAL`) for more details.
+    ///
+    /// ## Errors
+    ///
+    /// * `Io` for any error while applying the setting to the port.
+    pub fn set_carrier_detect(&mut self, honor: bool) -> Result<()> {
+        let mut termios = termios::get_termios(self.fd)?;
+        termios::set_carrier_detect(&mut termios, honor);
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        termios::set_termios(self.fd, &termios, self.baud_rate)?;
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        termios::set_termios(self.fd, &termios)?;
+        self.carrier_detect = honor;
+        Ok(())
+    }
+
+    /// Returns whether the port is in low-latency mode
+    ///
+    /// For FTDI adapters, this also requires their latency timer to be set to 1 ms.
+    ///
+    /// ## Errors
+    ///
+    /// * `Unsupported` if the driver does not support `TIOCGSERIAL` (like for pseudo terminals).
+    /// * `Io` for any other error while reading the settings of the port.
+    #[cfg(target_os = "linux")]
+    pub fn low_latency(&self) -> Result<bool> {
+        self.low_latency_with(Path::new("/sys"))
+    }
+
+    /// Returns whether the port is in low-latency mode, looking up the latency timer of FTDI
+    /// adapters in the sysfs file system mounted at `sysfs_root`
+    ///
+    /// See [`low_latency`](TTYPort::low_latency) and
+    /// [`EnumerationOptions::sysfs_root`](crate::EnumerationOptions::sysfs_root).
+    #[cfg(target_os = "linux")]
+    pub fn low_latency_with(&self, sysfs_root: &Path) -> Result<bool> {
+        let serial = ioctl::tiocgserial(self.fd)?;
+        if serial.flags & ioctl::ASYNC_LOW_LATENCY == 0 {
+            return Ok(false);
+        }
+        match self.ftdi_latency_timer_attribute(sysfs_root) {
+            Some(attribute) => Ok(read_latency_timer(&attribute)? == FTDI_LOW_LATENCY_TIMER),
+            None => Ok(true),
+        }
+    }
+
+    /// Sets whether to minimize the latency for receiving data
+    ///
+    /// This sets the `ASYNC_LOW_LATENCY` flag of the port. USB serial adapters buffer received
+    /// data before passing it on which hurts request/response protocols. For FTDI adapters
+    /// (`ftdi_sio`), their latency timer gets set to 1 ms in low-latency mode. Leaving low-latency
+    /// mode restores the latency timer from before entering it or sets the driver's default of
+    /// 16 ms if it has not been entered through this port. Dropping the port restores the latency
+    /// timer as well, but clones of the port (see [`try_clone_native`](TTYPort::try_clone_native))
+    /// don't. Like the latency timer, the `ASYNC_LOW_LATENCY` flag belongs to the device and
+    /// outlives the port.
+    ///
+    /// ## Errors
+    ///
+    /// * `Unsupported` if the driver does not support `TIOCSSERIAL` (like for pseudo terminals).
+    /// * `PermissionDenied` if the latency timer of an FTDI adapter is not writable. Its
+    ///   sysfs attribute is usually writable by root only. The description names the attribute
+    ///   which could be made accessible with a udev rule.
+    /// * `Io` for any other error while applying the setting to the port.
+    #[cfg(target_os = "linux")]
+    pub fn set_low_latency(&mut self, low_latency: bool) -> Result<()> {
+        self.set_low_latency_with(low_latency, Path::new("/sys"))
+    }
+
+    /// Sets whether to minimize the latency for receiving data, looking up the latency timer of
+    /// FTDI adapters in the sysfs file system mounted at `sysfs_root`
+    ///
+    /// See [`set_low_latency`](TTYPort::set_low_latency) and
+    /// [`EnumerationOptions::sysfs_root`](crate::EnumerationOptions::sysfs_root).
+    #[cfg(target_os = "linux")]
+    pub fn set_low_latency_with(&mut self, low_latency: bool, sysfs_root: &Path) -> Result<()> {
+        let mut serial = ioctl::tiocgserial(self.fd)?;
+        if low_latency {
+            serial.flags |= ioctl::ASYNC_LOW_LATENCY;
+        } else {
+            serial.flags &= !ioctl::ASYNC_LOW_LATENCY;
+        }
+        ioctl::tiocsserial(self.fd, &serial)?;
+
+        if let Some(attribute) = self.ftdi_latency_timer_attribute(sysfs_root) {
+            let mut saved = self.ftdi_latency_timer.take().map(|(_, value)| value);
+            let result = switch_latency_timer(&attribute, low_latency, &mut saved);
+            self.ftdi_latency_timer = saved.map(|value| (attribute, value));
+            result?;
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn ftdi_latency_timer_attribute(&self, sysfs_root: &Path) -> Option<PathBuf> {
+        let name = self.port_name.as_ref()?;
+        ftdi_latency_timer_attribute(sysfs_root, Path::new(name))
+    }
+
+    /// Returns hardware details of the UART like its type, FIFO size and base baud rate
+    ///
+    /// These come from the `TIOCGSERIAL` ioctl. The transmitter status comes from
+    /// `TIOCSERGETLSR`.
+    ///
+    /// ```no_run
+    /// let port = serialport::new("/dev/ttyS0", 115_200).open_native().unwrap();
+    /// let info = port.serial_info().unwrap();
+    /// println!(
+    ///     "{} with a FIFO of {} bytes",
+    ///     info.uart_name().unwrap_or("unknown UART"),
+    ///     info.xmit_fifo_size
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// * `Unsupported` if the driver does not provide UART details (like for pseudo terminals).
+    /// * `Io` for any other error while reading the details.
+    #[cfg(target_os = "linux")]
+    pub fn serial_info(&self) -> Result<SerialInfo> {
+        let serial = ioctl::tiocgserial(self.fd).map_err(|e| {
+            let description = format!("Failed to read UART details: {}", e.description);
+            e.with_description(description)
+        })?;
+        let port = if mem::size_of::<libc::c_ulong>() > mem::size_of::<libc::c_uint>() {
+            u64::from(serial.port) | u64::from(serial.port_high) << 32
+        } else {
+            u64::from(serial.port)
+        };
+        let transmitter_empty = ioctl::tiocsergetlsr(self.fd)
+            .ok()
+            .map(|status| status & ioctl::TIOCSER_TEMT != 0);
+
+        Ok(SerialInfo {
+            uart_type: serial.type_ as u32,
+            line: serial.line as u32,
+            port,
+            iomem_base: serial.iomem_base as usize as u64,
+            irq: serial.irq as u32,
+            xmit_fifo_size: serial.xmit_fifo_size as u32,
+            baud_base: serial.baud_base as u32,
+            custom_divisor: serial.custom_divisor as u32,
+            flags: serial.flags as u32,
+            transmitter_empty,
+        })
+    }
+
+    /// Returns the baud rate the UART actually produces for the current baud rate setting
+    ///
+    /// UARTs divide their base baud rate by an integer divisor and only approximate arbitrary
+    /// baud rates. This computes the nearest achievable rate from the base baud rate reported by
+    /// `TIOCGSERIAL` (like for 8250/16550 compatible UARTs). Drivers with fractional divisors
+    /// (like for FTDI adapters) may get closer to the requested rate than computed.
+    ///
+    /// ```no_run
+    /// let port = serialport::new("/dev/ttyS0", 250_000).open_native().unwrap();
+    /// let rate = port.effective_baud_rate().unwrap();
+    /// println!("{} baud ({:+.2} %)", rate.effective, rate.error_percent());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// * `Unsupported` if the driver does not report its base baud rate (like for pseudo
+    ///   terminals).
+    /// * `Io` for any other error while reading the settings of the port.
+    #[cfg(target_os = "linux")]
+    pub fn effective_baud_rate(&self) -> Result<EffectiveBaudRate> {
+        let requested = self.baud_rate()?;
+        let info = self.serial_info()?;
+        if info.baud_base == 0 {
+            return Err(Error::new(
+                ErrorKind::UNSUPPORTED,
+                "The driver does not report the base baud rate of the UART",
+            ));
+        }
+
+        // The legacy way of setting custom baud rates replaces 38400 baud with a custom divisor.
+        let custom_divisor = if requested == 38400
+            && info.flags as libc::c_int & ioctl::ASYNC_SPD_MASK == ioctl::ASYNC_SPD_CUST
+            && info.custom_divisor > 0
+        {
+            Some(info.custom_divisor)
+        } else {
+            None
+        };
+        Ok(EffectiveBaudRate::new(
+            requested,
+            info.baud_base,
+            custom_divisor,
+        ))
+    }
+
+    /// Returns whether the device is still connected
+    ///
+    /// This probes the port for a hang-up without blocking. A hang-up happens when a USB serial
+    /// adapter gets unplugged or the master side of a pseudo terminal gets closed. Reading and
+    /// writing fail with `ErrorKind::Disconnected` (with the feature `error-details`) afterwards.
+    /// The port needs to be reopened once the device is available again.
+    ///
+    /// ## Errors
+    ///
+    /// * `Io` for any error while probing the port.
+    pub fn is_connected(&self) -> Result<bool> {
+        let hung_up = super::poll::is_hung_up(self.fd)?;
+        Ok(!hung_up)
+    }
+
+    // The following methods intentionally shadow the methods of `SerialPort` with the same names,
+    // taking `&self` instead of `&mut self`. They behave the same, but can be called on a port
+    // shared between threads. The trait methods remain available with `SerialPort::set_timeout`
+    // and the like.
+
+    /// Sets the timeout for reading and writing through a shared reference
+    ///
+    /// This is the same as [`SerialPort::set_timeout`] but allows for changing the timeout of a
+    /// port shared between threads (like with `Arc<TTYPort>`).
+    ///
+    /// ## Errors
+    ///
+    /// This currently does not fail.
+    pub fn set_timeout(&self, timeout: Duration) -> Result<()> {
+        *self.timeout.lock().unwrap_or_else(PoisonError::into_inner) = timeout;
+        Ok(())
+    }
+
+    /// Sets the state of the RTS (Request To Send) control signal through a shared reference
+    ///
+    /// See [`SerialPort::write_request_to_send`].
+    pub fn write_request_to_send(&self, level: bool) -> Result<()> {
+        self.set_pin(SerialLines::REQUEST_TO_SEND, level)
+    }
+
+    /// Sets the state of the DTR (Data Terminal Ready) control signal through a shared
+    /// reference
+    ///
+    /// See [`SerialPort::write_data_terminal_ready`].
+    pub fn write_data_terminal_ready(&self, level: bool) -> Result<()> {
+        self.set_pin(SerialLines::DATA_TERMINAL_READY, level)
+    }
+
+    /// Reads the state of the CTS (Clear To Send) control signal through a shared reference
+    ///
+    /// See [`SerialPort::read_clear_to_send`].
+    pub fn read_clear_to_send(&self) -> Result<bool> {
+        self.read_pin(SerialLines::CLEAR_TO_SEND)
+    }
+
+    /// Reads the state of the Data Set Ready control signal through a shared reference
+    ///
+    /// See [`SerialPort::read_data_set_ready`].
+    pub fn read_data_set_ready(&self) -> Result<bool> {
+        self.read_pin(SerialLines::DATA_SET_READY)
+    }
+
+    /// Reads the state of the Ring Indicator control signal through a shared reference
+    ///
+    /// See [`SerialPort::read_ring_indicator`].
+    pub fn read_ring_indicator(&self) -> Result<bool> {
+        self.read_pin(SerialLines::RING)
+    }
+
+    /// Reads the state of the Carrier Detect control signal through a shared reference
+    ///
+    /// See [`SerialPort::read_carrier_detect`].
+    pub fn read_carrier_detect(&self) -> Result<bool> {
+        self.read_pin(SerialLines::DATA_CARRIER_DETECT)
+    }
+
+    fn current_timeout(&self) -> Duration {
+        *self.timeout.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn set_pin(&self, pin: ioctl::SerialLines, level: bool) -> Result<()> {
         if level {
             ioctl::tiocmbis(self.fd, pin)
         } else {
             ioctl::tiocmbic(self.fd, pin)
         }
+        .map_err(|e| self.device_error(e))
+    }
+
+    fn read_pin(&self, pin: ioctl::SerialLines) -> Result<bool> {
+        ioctl::tiocmget(self.fd)
+            .map(|pins| pins.contains(pin))
+            .map_err(|e| self.device_error(e))
+    }
+
+    /// Creates an error for a hung up device, optionally caused by `source`. This is `HangUp` when
+    /// honoring carrier detect and `Disconnected` otherwise.
+    fn hung_up_error(&self, source: Option<Error>) -> Error {
+        let error = if self.carrier_detect {
+            Error::new(ErrorKind::HANG_UP, HUNG_UP)
+        } else {
+            Error::new(ErrorKind::DISCONNECTED, super::poll::DISCONNECTED)
+        };
+        match source {
+            Some(source) => {
+                #[cfg(feature = "error-details")]
+                let error = match source.raw_os_error() {
+                    Some(code) => error.with_raw_os_error(code),
+                    None => error,
+                };
+                error.with_source(source)
+            }
+            None => error,
+        }
+    }
+
+    /// Converts errors from operations on a hung up device. Linux reports `EIO` for reading,
+    /// writing and most ioctls after a hang-up, so this checks whether the device has been hung
+    /// up instead of relying on the error.
+    fn device_error(&self, error: Error) -> Error {
+        if super::poll::is_hung_up(self.fd).unwrap_or(false) {
+            self.hung_up_error(Some(error))
+        } else {
+            error
+        }
     }
 
-    fn read_pin(&mut self, pin: ioctl::SerialLines) -> Result<bool> {
-        ioctl::tiocmget(self.fd).map(|pins| pins.contains(pin))
+    /// Converts errors from waiting for the device, which reports hang-ups as `Disconnected`.
+    fn wait_error(&self, error: io::Error) -> io::Error {
+        let error = Error::from(error);
+        if error.kind() == ErrorKind::DISCONNECTED {
+            io::Error::from(self.hung_up_error(None))
+        } else {
+            io::Error::from(error)
+        }
     }
 
     /// Create a pair of pseudo serial terminals
@@ -307,7 +1083,7 @@ impl TTYPort {
         let mut termios = MaybeUninit::uninit();
         let res = unsafe { crate::posix::tty::libc::tcgetattr(fd, termios.as_mut_ptr()) };
         if let Err(e) = nix::errno::Errno::result(res) {
-            close(fd);
+            close(fd, false);
             return Err(e.into());
         }
         let mut termios = unsafe { termios.assume_init() };
@@ -321,9 +1097,13 @@ impl TTYPort {
 
         let slave_tty = TTYPort {
             fd,
-            timeout: Duration::from_millis(100),
+            timeout: Mutex::new(Duration::from_millis(100)),
             exclusive: true,
+            strict: false,
+            carrier_detect: false,
             port_name: Some(ptty_name),
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             baud_rate,
         };
@@ -333,9 +1113,13 @@ impl TTYPort {
         // BSDs when used on the master port.
         let master_tty = TTYPort {
             fd: next_pty_fd.into_raw_fd(),
-            timeout: Duration::from_millis(100),
+            timeout: Mutex::new(Duration::from_millis(100)),
             exclusive: true,
+            strict: false,
+            carrier_detect: false,
             port_name: None,
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             baud_rate,
         };
@@ -371,17 +1155,153 @@ impl TTYPort {
         Ok(TTYPort {
             fd: fd_cloned,
             exclusive: self.exclusive,
+            strict: self.strict,
+            carrier_detect: self.carrier_detect,
             port_name: self.port_name.clone(),
-            timeout: self.timeout,
+            // Only the port which entered low-latency mode restores the latency timer.
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
+            timeout: Mutex::new(self.current_timeout()),
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             baud_rate: self.baud_rate,
         })
     }
 }
 
+/// Latency timer of FTDI adapters in low-latency mode (in milliseconds)
+#[cfg(target_os = "linux")]
+const FTDI_LOW_LATENCY_TIMER: u8 = 1;
+/// Default latency timer of the `ftdi_sio` driver (in milliseconds)
+#[cfg(target_os = "linux")]
+const FTDI_DEFAULT_LATENCY_TIMER: u8 = 16;
+
+/// Drivers with fractional baud rate divisors, which [`EffectiveBaudRate`] does not model
+#[cfg(target_os = "linux")]
+const FRACTIONAL_DIVISOR_DRIVERS: &[&str] = &["ftdi_sio"];
+
+/// Looks up the device of the port at `path` in the sysfs file system at `sysfs_root` and the
+/// name of its driver.
+#[cfg(target_os = "linux")]
+fn tty_device_driver(sysfs_root: &Path, path: &Path) -> Option<(PathBuf, String)> {
+    let name = path.canonicalize().ok()?.file_name()?.to_os_string();
+    let device = sysfs_root.join("class/tty").join(name).join("device");
+    let driver = device.join("driver").canonicalize().ok()?;
+    let driver = driver.file_name()?.to_str()?.to_string();
+    Some((device, driver))
+}
+
+/// Looks up the latency timer attribute of the FTDI adapter of the port at `path` in the sysfs
+/// file system at `sysfs_root`. Returns `None` if the port does not belong to an FTDI adapter.
+#[cfg(target_os = "linux")]
+fn ftdi_latency_timer_attribute(sysfs_root: &Path, path: &Path) -> Option<PathBuf> {
+    match tty_device_driver(sysfs_root, path) {
+        Some((device, driver)) if driver == "ftdi_sio" => Some(device.join("latency_timer")),
+        _ => None,
+    }
+}
+
+/// Checks whether the driver of the port at `path` uses fractional baud rate divisors.
+#[cfg(target_os = "linux")]
+fn has_fractional_divisors(sysfs_root: &Path, path: &Path) -> bool {
+    tty_device_driver(sysfs_root, path).map_or(false, |(_, driver)| {
+        FRACTIONAL_DIVISOR_DRIVERS.contains(&driver.as_str())
+    })
+}
+
+/// Checks the effective baud rate `rate` of the port at `path` against `tolerance` (in percent).
+/// Ports not reporting their base baud rate or with a driver using fractional divisors (looked up
+/// in the sysfs file system at `sysfs_root`) can't be checked and are accepted.
+#[cfg(target_os = "linux")]
+fn check_baud_rate_tolerance(
+    rate: Option<EffectiveBaudRate>,
+    tolerance: f64,
+    sysfs_root: &Path,
+    path: Option<&Path>,
+) -> Result<()> {
+    if path.map_or(false, |path| has_fractional_divisors(sysfs_root, path)) {
+        return Ok(());
+    }
+    match rate {
+        Some(rate) if rate.error_percent().abs() > tolerance => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "The UART produces {} baud instead of {} baud ({:+.2} %), exceeding the \
+                 tolerance of {} %",
+                rate.effective,
+                rate.requested,
+                rate.error_percent(),
+                tolerance
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Sets the latency timer at `attribute` to low latency, saving its previous value in `saved`, or
+/// back to the saved value (or the default).
+#[cfg(target_os = "linux")]
+fn switch_latency_timer(attribute: &Path, low_latency: bool, saved: &mut Option<u8>) -> Result<()> {
+    if low_latency {
+        let previous = read_latency_timer(attribute)?;
+        if previous != FTDI_LOW_LATENCY_TIMER {
+            *saved = Some(previous);
+        }
+        write_latency_timer(attribute, FTDI_LOW_LATENCY_TIMER)
+    } else {
+        let value = saved.unwrap_or(FTDI_DEFAULT_LATENCY_TIMER);
+        write_latency_timer(attribute, value)?;
+        *saved = None;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_latency_timer(attribute: &Path) -> Result<u8> {
+    let content = std::fs::read_to_string(attribute).map_err(|e| {
+        let description = format!(
+            "Failed to read the latency timer {}: {}",
+            attribute.display(),
+            e
+        );
+        Error::from(e).with_description(description)
+    })?;
+    content.trim().parse().map_err(|_| {
+        Error::new(
+            ErrorKind::Unknown,
+            format!(
+                "Invalid latency timer {:?} in {}",
+                content.trim(),
+                attribute.display()
+            ),
+        )
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn write_latency_timer(attribute: &Path, value: u8) -> Result<()> {
+    std::fs::write(attribute, value.to_string()).map_err(|e| {
+        let hint = if e.kind() == io::ErrorKind::PermissionDenied {
+            " (writing it requires root or a udev rule granting access)"
+        } else {
+            ""
+        };
+        let description = format!(
+            "Failed to set the latency timer {}{}: {}",
+            attribute.display(),
+            hint,
+            e
+        );
+        Error::from(e).with_description(description)
+    })
+}
+
 impl Drop for TTYPort {
     fn drop(&mut self) {
-        close(self.fd);
+        #[cfg(target_os = "linux")]
+        if let Some((attribute, value)) = self.ftdi_latency_timer.take() {
+            let _ = write_latency_timer(&attribute, value);
+        }
+        close(self.fd, self.exclusive);
     }
 }
 
@@ -417,11 +1337,15 @@ impl FromRawFd for TTYPort {
     unsafe fn from_raw_fd(fd: RawFd) -> Self {
         TTYPort {
             fd,
-            timeout: Duration::from_millis(100),
+            timeout: Mutex::new(Duration::from_millis(100)),
             exclusive: ioctl::tiocexcl(fd).is_ok(),
+            strict: false,
+            carrier_detect: honors_carrier_detect(fd),
             // It is not trivial to get the file path corresponding to a file descriptor.
             // We'll punt on it and set it to `None` here.
             port_name: None,
+            #[cfg(target_os = "linux")]
+            ftdi_latency_timer: None,
             // It's not guaranteed that the baud rate in the `termios` struct is correct, as
             // setting an arbitrary baud rate via the `iossiospeed` ioctl overrides that value,
             // but extract that value anyways as a best-guess of the actual baud rate.
@@ -431,27 +1355,71 @@ impl FromRawFd for TTYPort {
     }
 }
 
-impl io::Read for TTYPort {
+// The I/O safety traits are available with Rust 1.63 and later.
+#[rustversion::since(1.63)]
+impl std::os::unix::io::AsFd for TTYPort {
+    fn as_fd(&self) -> std::os::unix::io::BorrowedFd<'_> {
+        // Safe as the file descriptor stays open for the lifetime of the port.
+        unsafe { std::os::unix::io::BorrowedFd::borrow_raw(self.fd) }
+    }
+}
+
+/// Adopts the file descriptor like [`from_raw_fd`](FromRawFd::from_raw_fd).
+#[rustversion::since(1.63)]
+impl From<std::os::unix::io::OwnedFd> for TTYPort {
+    fn from(fd: std::os::unix::io::OwnedFd) -> Self {
+        // Safe as the ownership of the file descriptor gets transferred to the port.
+        unsafe { TTYPort::from_raw_fd(fd.into_raw_fd()) }
+    }
+}
+
+#[rustversion::since(1.63)]
+impl From<TTYPort> for std::os::unix::io::OwnedFd {
+    fn from(port: TTYPort) -> Self {
+        // Safe as the ownership of the file descriptor gets transferred from the port.
+        unsafe { std::os::unix::io::OwnedFd::from_raw_fd(port.into_raw_fd()) }
+    }
+}
+
+/// Reads through a shared reference, like for `&TcpStream`. This allows for sharing a port between
+/// threads (like with `Arc<TTYPort>`) without locking it for each call.
+impl io::Read for &TTYPort {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if let Err(e) = super::poll::wait_read_fd(self.fd, self.timeout) {
-            return Err(io::Error::from(Error::from(e)));
+        if let Err(e) = super::poll::wait_read_fd(self.fd, self.current_timeout()) {
+            return Err(self.wait_error(e));
         }
 
-        nix::unistd::read(self.fd, buf).map_err(|e| io::Error::from(Error::from(e)))
+        match nix::unistd::read(self.fd, buf) {
+            // The device was ready for reading but has nothing to read. This happens after a
+            // hang-up, but also for an end of file character in canonical mode or with `VMIN`
+            // set to zero (like for adopted ports). Only report the former as an error.
+            Ok(0) if !buf.is_empty() && super::poll::is_hung_up(self.fd).unwrap_or(false) => {
+                Err(io::Error::from(self.hung_up_error(None)))
+            }
+            Ok(n) => Ok(n),
+            Err(e) => Err(io::Error::from(self.device_error(e.into()))),
+        }
     }
 }
 
-impl io::Write for TTYPort {
+impl io::Read for TTYPort {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+/// Writes through a shared reference, like for `&TcpStream`.
+impl io::Write for &TTYPort {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if let Err(e) = super::poll::wait_write_fd(self.fd, self.timeout) {
-            return Err(io::Error::from(Error::from(e)));
+        if let Err(e) = super::poll::wait_write_fd(self.fd, self.current_timeout()) {
+            return Err(self.wait_error(e));
         }
 
-        nix::unistd::write(self.fd, buf).map_err(|e| io::Error::from(Error::from(e)))
+        nix::unistd::write(self.fd, buf).map_err(|e| io::Error::from(self.device_error(e.into())))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        let timeout = Instant::now() + self.timeout;
+        let timeout = Instant::now() + self.current_timeout();
         loop {
             return match nix::sys::termios::tcdrain(self.fd) {
                 Ok(_) => Ok(()),
@@ -467,12 +1435,25 @@ impl io::Write for TTYPort {
                         ))
                     }
                 }
-                Err(_) => Err(io::Error::new(io::ErrorKind::Other, "flush failed")),
+                Err(e) => Err(io::Error::from(self.device_error(e.into()))),
             };
         }
     }
 }
 
+impl io::Write for TTYPort {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+/// Description of errors due to a lost carrier
+const HUNG_UP: &str = "The carrier has been lost";
+
 impl SerialPort for TTYPort {
     fn name(&self) -> Option<String> {
         self.port_name.clone()
@@ -644,7 +1625,7 @@ impl SerialPort for TTYPort {
     }
 
     fn timeout(&self) -> Duration {
-        self.timeout
+        self.current_timeout()
     }
 
     #[cfg(any(
@@ -658,7 +1639,8 @@ impl SerialPort for TTYPort {
     fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
         let mut termios = termios::get_termios(self.fd)?;
         termios::set_baud_rate(&mut termios, baud_rate)?;
-        termios::set_termios(self.fd, &termios)
+        termios::set_termios(self.fd, &termios)?;
+        self.verify_setting("baud rate", baud_rate, |port| port.baud_rate())
     }
 
     // Mac OS needs special logic for setting arbitrary baud rates.
@@ -673,65 +1655,68 @@ impl SerialPort for TTYPort {
         let mut termios = termios::get_termios(self.fd)?;
         termios::set_flow_control(&mut termios, flow_control);
         #[cfg(any(target_os = "ios", target_os = "macos"))]
-        return termios::set_termios(self.fd, &termios, self.baud_rate);
+        termios::set_termios(self.fd, &termios, self.baud_rate)?;
         #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-        return termios::set_termios(self.fd, &termios);
+        termios::set_termios(self.fd, &termios)?;
+        self.verify_setting("flow control", flow_control, |port| port.flow_control())
     }
 
     fn set_parity(&mut self, parity: Parity) -> Result<()> {
         let mut termios = termios::get_termios(self.fd)?;
         termios::set_parity(&mut termios, parity);
         #[cfg(any(target_os = "ios", target_os = "macos"))]
-        return termios::set_termios(self.fd, &termios, self.baud_rate);
+        termios::set_termios(self.fd, &termios, self.baud_rate)?;
         #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-        return termios::set_termios(self.fd, &termios);
+        termios::set_termios(self.fd, &termios)?;
+        self.verify_setting("parity", parity, |port| port.parity())
     }
 
     fn set_data_bits(&mut self, data_bits: DataBits) -> Result<()> {
         let mut termios = termios::get_termios(self.fd)?;
         termios::set_data_bits(&mut termios, data_bits);
         #[cfg(any(target_os = "ios", target_os = "macos"))]
-        return termios::set_termios(self.fd, &termios, self.baud_rate);
+        termios::set_termios(self.fd, &termios, self.baud_rate)?;
         #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-        return termios::set_termios(self.fd, &termios);
+        termios::set_termios(self.fd, &termios)?;
+        self.verify_setting("data bits", data_bits, |port| port.data_bits())
     }
 
     fn set_stop_bits(&mut self, stop_bits: StopBits) -> Result<()> {
         let mut termios = termios::get_termios(self.fd)?;
         termios::set_stop_bits(&mut termios, stop_bits);
         #[cfg(any(target_os = "ios", target_os = "macos"))]
-        return termios::set_termios(self.fd, &termios, self.baud_rate);
+        termios::set_termios(self.fd, &termios, self.baud_rate)?;
         #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-        return termios::set_termios(self.fd, &termios);
+        termios::set_termios(self.fd, &termios)?;
+        self.verify_setting("stop bits", stop_bits, |port| port.stop_bits())
     }
 
     fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
-        self.timeout = timeout;
-        Ok(())
+        TTYPort::set_timeout(self, timeout)
     }
 
     fn write_request_to_send(&mut self, level: bool) -> Result<()> {
-        self.set_pin(SerialLines::REQUEST_TO_SEND, level)
+        TTYPort::write_request_to_send(self, level)
     }
 
     fn write_data_terminal_ready(&mut self, level: bool) -> Result<()> {
-        self.set_pin(SerialLines::DATA_TERMINAL_READY, level)
+        TTYPort::write_data_terminal_ready(self, level)
     }
 
     fn read_clear_to_send(&mut self) -> Result<bool> {
-        self.read_pin(SerialLines::CLEAR_TO_SEND)
+        TTYPort::read_clear_to_send(self)
     }
 
     fn read_data_set_ready(&mut self) -> Result<bool> {
-        self.read_pin(SerialLines::DATA_SET_READY)
+        TTYPort::read_data_set_ready(self)
     }
 
     fn read_ring_indicator(&mut self) -> Result<bool> {
-        self.read_pin(SerialLines::RING)
+        TTYPort::read_ring_indicator(self)
     }
 
     fn read_carrier_detect(&mut self) -> Result<bool> {
-        self.read_pin(SerialLines::DATA_CARRIER_DETECT)
+        TTYPort::read_carrier_detect(self)
     }
 
     fn bytes_to_read(&self) -> Result<u32> {
@@ -786,7 +1771,7 @@ fn test_ttyport_into_raw_fd() {
     let mut termios = MaybeUninit::uninit();
     let res = unsafe { nix::libc::tcgetattr(master_fd, termios.as_mut_ptr()) };
     if res != 0 {
-        close(master_fd);
+        close(master_fd, true);
         panic!("tcgetattr on the master port failed");
     }
 
@@ -794,9 +1779,136 @@ fn test_ttyport_into_raw_fd() {
     let slave_fd = slave.into_raw_fd();
     let res = unsafe { nix::libc::tcgetattr(slave_fd, termios.as_mut_ptr()) };
     if res != 0 {
-        close(slave_fd);
+        close(slave_fd, true);
         panic!("tcgetattr on the master port failed");
     }
-    close(master_fd);
-    close(slave_fd);
+    close(master_fd, true);
+    close(slave_fd, true);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_switch_ftdi_latency_timer() {
+    use crate::tests::fs::TempDir;
+    use std::os::unix::fs::PermissionsExt;
+
+    let sys = TempDir::new("sys");
+    let dev = TempDir::new("dev");
+    let ftdi = sys.dir("devices/usb1/1-2/1-2:1.0/ttyUSB0");
+    sys.symlink(
+        "devices/usb1/1-2/1-2:1.0/ttyUSB0/driver",
+        sys.dir("bus/usb-serial/drivers/ftdi_sio"),
+    );
+    sys.file("devices/usb1/1-2/1-2:1.0/ttyUSB0/latency_timer", "16\n");
+    sys.symlink("class/tty/ttyUSB0/device", &ftdi);
+    let cp210x = sys.dir("devices/usb1/1-3/1-3:1.0/ttyUSB1");
+    sys.symlink(
+        "devices/usb1/1-3/1-3:1.0/ttyUSB1/driver",
+        sys.dir("bus/usb-serial/drivers/cp210x"),
+    );
+    sys.symlink("class/tty/ttyUSB1/device", &cp210x);
+    let usb0 = dev.file("ttyUSB0", "");
+    let usb1 = dev.file("ttyUSB1", "");
+    let alias = dev.symlink(
+        "serial/by-id/usb-FTDI_FT232R_USB_UART_A50285BI-if00-port0",
+        &usb0,
+    );
+
+    let latency_timer = ftdi.join("latency_timer");
+    assert_eq!(
+        ftdi_latency_timer_attribute(sys.path(), &alias),
+        Some(sys.path().join("class/tty/ttyUSB0/device/latency_timer"))
+    );
+    assert_eq!(ftdi_latency_timer_attribute(sys.path(), &usb1), None);
+    assert!(has_fractional_divisors(sys.path(), &alias));
+    assert!(!has_fractional_divisors(sys.path(), &usb1));
+
+    // The latency timer gets restored when leaving low-latency mode.
+    let mut saved = None;
+    std::fs::write(&latency_timer, "4\n").unwrap();
+    switch_latency_timer(&latency_timer, true, &mut saved).unwrap();
+    assert_eq!(std::fs::read_to_string(&latency_timer).unwrap(), "1");
+    switch_latency_timer(&latency_timer, true, &mut saved).unwrap();
+    assert_eq!(saved, Some(4));
+    switch_latency_timer(&latency_timer, false, &mut saved).unwrap();
+    assert_eq!(std::fs::read_to_string(&latency_timer).unwrap(), "4");
+    assert_eq!(saved, None);
+    // Without a saved value, the default gets set.
+    switch_latency_timer(&latency_timer, false, &mut saved).unwrap();
+    assert_eq!(std::fs::read_to_string(&latency_timer).unwrap(), "16");
+
+    // Dropping the port which saved the latency timer restores it, dropping its clones doesn't.
+    let (mut port, _slave) = TTYPort::pair().unwrap();
+    port.ftdi_latency_timer = Some((latency_timer.clone(), 4));
+    drop(port.try_clone_native().unwrap());
+    assert_eq!(std::fs::read_to_string(&latency_timer).unwrap(), "16");
+    drop(port);
+    assert_eq!(std::fs::read_to_string(&latency_timer).unwrap(), "4");
+
+    // Root can write the attribute regardless of its permissions.
+    if unsafe { libc::geteuid() } != 0 {
+        std::fs::set_permissions(&latency_timer, std::fs::Permissions::from_mode(0o444)).unwrap();
+        let error = switch_latency_timer(&latency_timer, true, &mut saved).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PERMISSION_DENIED);
+        assert!(error.description.contains("latency_timer"));
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_check_baud_rate_tolerance() {
+    use crate::tests::fs::TempDir;
+
+    let sys = TempDir::new("sys");
+    let dev = TempDir::new("dev");
+    let ftdi = sys.dir("devices/usb1/1-2/1-2:1.0/ttyUSB0");
+    sys.symlink(
+        "devices/usb1/1-2/1-2:1.0/ttyUSB0/driver",
+        sys.dir("bus/usb-serial/drivers/ftdi_sio"),
+    );
+    sys.symlink("class/tty/ttyUSB0/device", &ftdi);
+    let uart = sys.dir("devices/platform/serial8250");
+    sys.symlink(
+        "devices/platform/serial8250/driver",
+        sys.dir("bus/platform/drivers/serial8250"),
+    );
+    sys.symlink("class/tty/ttyS0/device", &uart);
+    let usb0 = dev.file("ttyUSB0", "");
+    let s0 = dev.file("ttyS0", "");
+
+    // Dividing 921600 baud down to 100000 baud results in 102400 baud (+2.4 %).
+    let off = Some(EffectiveBaudRate::new(100_000, 921_600, None));
+    let error = check_baud_rate_tolerance(off, 2.0, sys.path(), Some(&s0)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert!(check_baud_rate_tolerance(off, 3.0, sys.path(), Some(&s0)).is_ok());
+    // Drivers with fractional divisors are skipped, as are ports without a base baud rate.
+    assert!(check_baud_rate_tolerance(off, 2.0, sys.path(), Some(&usb0)).is_ok());
+    assert!(check_baud_rate_tolerance(None, 2.0, sys.path(), Some(&s0)).is_ok());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_effective_baud_rate() {
+    // A 16550 with a 1.8432 MHz crystal produces standard baud rates exactly.
+    let rate = EffectiveBaudRate::new(115_200, 115_200, None);
+    assert_eq!(rate.effective, 115_200);
+    assert_eq!(rate.error_percent(), 0.0);
+    assert_eq!(EffectiveBaudRate::new(9600, 115_200, None).effective, 9600);
+
+    // But not the ones used by 3D printers and DMX.
+    let rate = EffectiveBaudRate::new(250_000, 115_200, None);
+    assert_eq!(rate.effective, 115_200);
+    assert!((rate.error_percent() + 53.92).abs() < 0.01);
+    let rate = EffectiveBaudRate::new(250_000, 1_500_000, None);
+    assert_eq!(rate.effective, 250_000);
+
+    // The nearest divisor is picked.
+    let rate = EffectiveBaudRate::new(100_000, 921_600, None);
+    assert_eq!(rate.effective, 102_400);
+    assert!((rate.error_percent() - 2.4).abs() < 0.01);
+
+    // A custom divisor replaces 38400 baud.
+    let rate = EffectiveBaudRate::new(38_400, 115_200, Some(2));
+    assert_eq!(rate.effective, 57_600);
+    assert!((rate.error_percent() - 50.0).abs() < 0.01);
 }
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+
+/// Directories below `/dev` which get watched with inotify besides `/dev` itself. udev creates the
+/// persistent links to a serial device in them only after the device node.
+#[cfg(target_os = "linux")]
+const LINK_DIRS: [&str; 3] = ["serial", "serial/by-id", "serial/by-path"];
+
+/// Wakes up a [`PortWatcher`](crate::PortWatcher) or
+/// [`open_wait`](crate::SerialPortBuilder::open_wait) when serial devices might have been added
+/// or removed.
+///
+/// On Linux this is a udev monitor (if the `libudev` feature is enabled) or an inotify watch on
+/// `/dev` and the directories of the persistent links in `/dev/serial`. If neither of them is
+/// available, this falls back to polling.
+pub(crate) enum HotplugSignal {
+    #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+    Udev(libudev::MonitorSocket),
+    #[cfg(target_os = "linux")]
+    Inotify(nix::sys::inotify::Inotify, PathBuf),
+    Poll,
+}
+
+impl HotplugSignal {
+    pub(crate) fn new() -> Self {
+        #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+        if let Some(socket) = Self::udev_monitor() {
+            return HotplugSignal::Udev(socket);
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(signal) = Self::inotify(Path::new("/dev")) {
+            return signal;
+        }
+
+        HotplugSignal::Poll
+    }
+
+    #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+    fn udev_monitor() -> Option<libudev::MonitorSocket> {
+        let context = libudev::Context::new().ok()?;
+        let mut monitor = libudev::Monitor::new(&context).ok()?;
+        monitor.match_subsystem("tty").ok()?;
+        monitor.listen().ok()
+    }
+
+    /// Watches `dev_root` (like `/dev`) with inotify. Returns `None` if it can't be watched.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn inotify(dev_root: &Path) -> Option<Self> {
+        use nix::sys::inotify::{InitFlags, Inotify};
+
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC).ok()?;
+        // Closes the inotify instance again when dropped on failure.
+        let signal = HotplugSignal::Inotify(inotify, dev_root.to_path_buf());
+        if add_watches(inotify, dev_root) {
+            Some(signal)
+        } else {
+            None
+        }
+    }
+
+    /// Waits for up to `timeout` for a notification. Returns `true` if serial devices might have
+    /// changed since the last call and `false` if nothing happened.
+    ///
+    /// With inotify, this returns `true` on timeouts as well as links might have appeared in a
+    /// directory which was not watched yet.
+    pub(crate) fn wait(&mut self, timeout: Duration) -> bool {
+        match self {
+            #[cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+            HotplugSignal::Udev(socket) => {
+                if super::poll::wait_read_fd(socket.as_raw_fd(), timeout).is_err() {
+                    return false;
+                }
+                while socket.receive_event().is_some() {}
+                true
+            }
+            #[cfg(target_os = "linux")]
+            HotplugSignal::Inotify(inotify, dev_root) => {
+                if super::poll::wait_read_fd(inotify.as_raw_fd(), timeout).is_ok() {
+                    while inotify.read_events().is_ok() {}
+                }
+                // The directories of the links come and go with the devices.
+                add_watches(*inotify, dev_root);
+                true
+            }
+            HotplugSignal::Poll => {
+                std::thread::sleep(timeout);
+                true
+            }
+        }
+    }
+}
+
+impl Drop for HotplugSignal {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let HotplugSignal::Inotify(inotify, _) = self {
+            let _ = nix::unistd::close(inotify.as_raw_fd());
+        }
+    }
+}
+
+/// Watches `dev_root` and the existing [`LINK_DIRS`] below it for entries being created or removed.
+/// Watching a directory again keeps its watch. Returns `false` if `dev_root` can't be watched.
+#[cfg(target_os = "linux")]
+fn add_watches(inotify: nix::sys::inotify::Inotify, dev_root: &Path) -> bool {
+    use nix::sys::inotify::AddWatchFlags;
+
+    let flags = AddWatchFlags::IN_CREATE
+        | AddWatchFlags::IN_DELETE
+        | AddWatchFlags::IN_MOVED_FROM
+        | AddWatchFlags::IN_MOVED_TO;
+    if inotify.add_watch(dev_root, flags).is_err() {
+        return false;
+    }
+    for dir in LINK_DIRS.iter() {
+        let _ = inotify.add_watch(&dev_root.join(dir), flags);
+    }
+    true
+}
@@ -0,0 +1,627 @@
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use crate::{
+    ClearBuffer, DataBits, Error, ErrorKind, FlowControl, Parity, Result, SerialPort,
+    SerialPortBuilder, SerialPortType, StopBits, UsbPortFilter,
+};
+
+/// How often and how fast [`ReconnectingPort`] retries reopening a port
+///
+/// The delay before the first attempt is `initial` and doubles with every failed attempt up to
+/// `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first attempt
+    pub initial: Duration,
+    /// Maximum delay between attempts
+    pub max: Duration,
+    /// Number of attempts before giving up or `None` for retrying indefinitely
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(5),
+            max_attempts: None,
+        }
+    }
+}
+
+/// A state transition of a [`ReconnectingPort`]
+#[derive(Debug, Clone)]
+pub enum ReconnectEvent {
+    /// The device has been disconnected.
+    Disconnected(Error),
+    /// Reopening the port is about to be attempted after the given delay.
+    Reconnecting {
+        /// Number of the attempt starting at 1
+        attempt: u32,
+        /// Delay before this attempt
+        delay: Duration,
+    },
+    /// Reopening the port failed.
+    ReconnectFailed {
+        /// Number of the attempt starting at 1
+        attempt: u32,
+        /// Why reopening the port failed
+        error: Error,
+    },
+    /// The port has been reopened at the given path and its settings have been restored.
+    Reconnected {
+        /// The path of the reopened port which may differ from the one before
+        port_name: String,
+    },
+}
+
+type EventHandler = Box<dyn FnMut(&ReconnectEvent) + Send>;
+type Opener = Box<dyn FnMut(SerialPortBuilder) -> Result<Box<dyn SerialPort>> + Send>;
+
+/// A serial port which transparently reopens its device after it has been disconnected
+///
+/// When reading, writing or changing settings fails because the device has been disconnected
+/// (like when unplugging a USB serial adapter), the port gets reopened with the settings of the
+/// builder it has been opened with. Settings changed through [`SerialPort`] and the states of RTS,
+/// DTR and break are restored too. Afterwards, the failed operation is retried once.
+///
+/// USB serial adapters with a serial number are looked up by their identity (VID, PID, serial
+/// number and interface) and may reappear at a different path. Other ports are reopened at
+/// their path.
+///
+/// Ports honoring carrier detect (see [`SerialPortBuilder::carrier_detect`]) report their device
+/// going away as a hang-up (`ErrorKind::HangUp` with the feature `error-details`), which can't be
+/// told apart from losing the carrier. Both get reopened like a disconnect.
+///
+/// Reconnecting blocks the operation which noticed the disconnect until the port has been
+/// reopened or the [`Backoff`] gives up. Operations taking `&self` (like
+/// [`bytes_to_read`](SerialPort::bytes_to_read)) do not reconnect and fail with
+/// `ErrorKind::Disconnected` (`ErrorKind::Io` with `BrokenPipe` without the feature
+/// `error-details`) until a reconnect happened.
+///
+/// ```no_run
+/// use serialport::{ReconnectEvent, ReconnectingPort};
+/// use std::io::Read;
+///
+/// let mut port = ReconnectingPort::open(serialport::new("/dev/ttyUSB0", 115_200))
+///     .expect("Failed to open port")
+///     .on_event(|event| match event {
+///         ReconnectEvent::Disconnected(e) => eprintln!("disconnected: {}", e),
+///         ReconnectEvent::Reconnected { port_name } => eprintln!("reconnected at {}", port_name),
+///         _ => (),
+///     });
+///
+/// let mut buffer = [0u8; 64];
+/// loop {
+///     match port.read(&mut buffer) {
+///         Ok(n) => println!("{:?}", &buffer[..n]),
+///         Err(e) if e.kind() == std::io::ErrorKind::TimedOut => (),
+///         Err(e) => panic!("{}", e),
+///     }
+/// }
+/// ```
+pub struct ReconnectingPort {
+    builder: SerialPortBuilder,
+    identity: Option<UsbPortFilter>,
+    port: Option<Box<dyn SerialPort>>,
+    open: Opener,
+    backoff: Backoff,
+    on_event: Option<EventHandler>,
+    request_to_send: Option<bool>,
+    data_terminal_ready: Option<bool>,
+    break_set: Cell<bool>,
+}
+
+impl fmt::Debug for ReconnectingPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectingPort")
+            .field("builder", &self.builder)
+            .field("identity", &self.identity)
+            .field("connected", &self.port.is_some())
+            .field("backoff", &self.backoff)
+            .finish()
+    }
+}
+
+impl ReconnectingPort {
+    /// Opens the port configured by `builder`
+    ///
+    /// If the port belongs to a USB serial adapter with a serial number, its identity is used for
+    /// finding it again after a disconnect.
+    ///
+    /// ## Errors
+    ///
+    /// The same as for [`SerialPortBuilder::open`].
+    pub fn open(builder: SerialPortBuilder) -> Result<Self> {
+        Self::open_with(builder, Box::new(SerialPortBuilder::open))
+    }
+
+    /// Opens the port configured by `builder` with `open`, which is used for reopening it too.
+    fn open_with(builder: SerialPortBuilder, mut open: Opener) -> Result<Self> {
+        let port = open(builder.clone())?;
+        let identity = match crate::port_info(&builder.path).map(|info| info.port_type) {
+            Ok(SerialPortType::UsbPort(info)) => info.serial_number.as_ref().map(|serial| {
+                let filter = UsbPortFilter::new()
+                    .vid(info.vid)
+                    .pid(info.pid)
+                    .serial_number(serial.as_str());
+                #[cfg(feature = "usbportinfo-interface")]
+                let filter = match info.interface {
+                    Some(interface) => filter.interface(interface),
+                    None => filter,
+                };
+                filter
+            }),
+            _ => None,
+        };
+
+        Ok(ReconnectingPort {
+            builder,
+            identity,
+            port: Some(port),
+            open,
+            backoff: Backoff::default(),
+            on_event: None,
+            request_to_send: None,
+            data_terminal_ready: None,
+            break_set: Cell::new(false),
+        })
+    }
+
+    /// Sets the USB identity used for finding the port after a disconnect
+    #[must_use]
+    pub fn usb_identity(mut self, filter: UsbPortFilter) -> Self {
+        self.identity = Some(filter);
+        self
+    }
+
+    /// Sets how often and how fast to retry reopening the port
+    #[must_use]
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets a handler getting called for every [`ReconnectEvent`]
+    #[must_use]
+    pub fn on_event<F: FnMut(&ReconnectEvent) + Send + 'static>(mut self, handler: F) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    /// Returns whether the port is currently open
+    pub fn is_connected(&self) -> bool {
+        self.port.is_some()
+    }
+
+    /// Reopens the port and restores its settings
+    ///
+    /// This is done automatically when noticing a disconnect. It retries according to the
+    /// [`Backoff`].
+    ///
+    /// ## Errors
+    ///
+    /// The error of the last attempt if the [`Backoff`] gave up.
+    pub fn reconnect(&mut self) -> Result<()> {
+        self.port = None;
+        let mut delay = self.backoff.initial;
+        let mut attempt = 1;
+        loop {
+            self.emit(ReconnectEvent::Reconnecting { attempt, delay });
+            thread::sleep(delay);
+
+            match self.reopen() {
+                Ok(port) => {
+                    self.port = Some(port);
+                    let port_name = self.builder.path.clone();
+                    self.emit(ReconnectEvent::Reconnected { port_name });
+                    return Ok(());
+                }
+                Err(error) => {
+                    self.emit(ReconnectEvent::ReconnectFailed {
+                        attempt,
+                        error: error.clone(),
+                    });
+                    if self
+                        .backoff
+                        .max_attempts
+                        .map_or(false, |max| attempt >= max)
+                    {
+                        return Err(error);
+                    }
+                }
+            }
+
+            attempt += 1;
+            delay = (delay * 2).min(self.backoff.max);
+        }
+    }
+
+    fn reopen(&mut self) -> Result<Box<dyn SerialPort>> {
+        if let Some(ref filter) = self.identity {
+            self.builder.path = crate::find(filter)?.port_name;
+        }
+
+        let mut port = (self.open)(self.builder.clone())?;
+        if let Some(level) = self.request_to_send {
+            port.write_request_to_send(level)?;
+        }
+        if let Some(level) = self.data_terminal_ready {
+            port.write_data_terminal_ready(level)?;
+        }
+        if self.break_set.get() {
+            port.set_break()?;
+        }
+        Ok(port)
+    }
+
+    fn emit(&mut self, event: ReconnectEvent) {
+        if let Some(ref mut handler) = self.on_event {
+            handler(&event);
+        }
+    }
+
+    /// Returns the open port or the error for operations which can't reconnect.
+    fn port(&self) -> Result<&dyn SerialPort> {
+        match self.port {
+            Some(ref port) => Ok(port.as_ref()),
+            None => Err(Error::new(
+                ErrorKind::DISCONNECTED,
+                "The device has been disconnected",
+            )),
+        }
+    }
+
+    /// Runs `operation` on the port, reconnecting and retrying once if the device has been
+    /// disconnected.
+    fn with_port<T, F>(&mut self, mut operation: F) -> Result<T>
+    where
+        F: FnMut(&mut dyn SerialPort) -> Result<T>,
+    {
+        if let Some(ref mut port) = self.port {
+            match operation(port.as_mut()) {
+                Err(error) if is_disconnect(&error) => {
+                    self.emit(ReconnectEvent::Disconnected(error));
+                }
+                result => return result,
+            }
+        }
+
+        self.reconnect()?;
+        operation(self.port.as_mut().expect("reconnected").as_mut())
+    }
+}
+
+/// Checks whether `error` indicates that the device has gone away. This includes a hang-up as
+/// ports honoring carrier detect report their device going away this way.
+fn is_disconnect(error: &Error) -> bool {
+    [
+        ErrorKind::DISCONNECTED,
+        ErrorKind::HANG_UP,
+        ErrorKind::NoDevice,
+    ]
+    .contains(&error.kind())
+}
+
+impl io::Read for ReconnectingPort {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.with_port(|port| port.read(buf).map_err(Error::from))
+            .map_err(io::Error::from)
+    }
+}
+
+impl io::Write for ReconnectingPort {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.with_port(|port| port.write(buf).map_err(Error::from))
+            .map_err(io::Error::from)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.with_port(|port| port.flush().map_err(Error::from))
+            .map_err(io::Error::from)
+    }
+}
+
+impl SerialPort for ReconnectingPort {
+    fn name(&self) -> Option<String> {
+        Some(self.builder.path.clone())
+    }
+
+    fn baud_rate(&self) -> Result<u32> {
+        self.port()?.baud_rate()
+    }
+
+    fn data_bits(&self) -> Result<DataBits> {
+        self.port()?.data_bits()
+    }
+
+    fn flow_control(&self) -> Result<FlowControl> {
+        self.port()?.flow_control()
+    }
+
+    fn parity(&self) -> Result<Parity> {
+        self.port()?.parity()
+    }
+
+    fn stop_bits(&self) -> Result<StopBits> {
+        self.port()?.stop_bits()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.builder.timeout
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        self.with_port(|port| port.set_baud_rate(baud_rate))?;
+        self.builder.baud_rate = baud_rate;
+        Ok(())
+    }
+
+    fn set_data_bits(&mut self, data_bits: DataBits) -> Result<()> {
+        self.with_port(|port| port.set_data_bits(data_bits))?;
+        self.builder.data_bits = data_bits;
+        Ok(())
+    }
+
+    fn set_flow_control(&mut self, flow_control: FlowControl) -> Result<()> {
+        self.with_port(|port| port.set_flow_control(flow_control))?;
+        self.builder.flow_control = flow_control;
+        Ok(())
+    }
+
+    fn set_parity(&mut self, parity: Parity) -> Result<()> {
+        self.with_port(|port| port.set_parity(parity))?;
+        self.builder.parity = parity;
+        Ok(())
+    }
+
+    fn set_stop_bits(&mut self, stop_bits: StopBits) -> Result<()> {
+        self.with_port(|port| port.set_stop_bits(stop_bits))?;
+        self.builder.stop_bits = stop_bits;
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.with_port(|port| port.set_timeout(timeout))?;
+        self.builder.timeout = timeout;
+        Ok(())
+    }
+
+    fn write_request_to_send(&mut self, level: bool) -> Result<()> {
+        self.with_port(|port| port.write_request_to_send(level))?;
+        self.request_to_send = Some(level);
+        Ok(())
+    }
+
+    fn write_data_terminal_ready(&mut self, level: bool) -> Result<()> {
+        self.with_port(|port| port.write_data_terminal_ready(level))?;
+        self.data_terminal_ready = Some(level);
+        Ok(())
+    }
+
+    fn read_clear_to_send(&mut self) -> Result<bool> {
+        self.with_port(|port| port.read_clear_to_send())
+    }
+
+    fn read_data_set_ready(&mut self) -> Result<bool> {
+        self.with_port(|port| port.read_data_set_ready())
+    }
+
+    fn read_ring_indicator(&mut self) -> Result<bool> {
+        self.with_port(|port| port.read_ring_indicator())
+    }
+
+    fn read_carrier_detect(&mut self) -> Result<bool> {
+        self.with_port(|port| port.read_carrier_detect())
+    }
+
+    fn bytes_to_read(&self) -> Result<u32> {
+        self.port()?.bytes_to_read()
+    }
+
+    fn bytes_to_write(&self) -> Result<u32> {
+        self.port()?.bytes_to_write()
+    }
+
+    fn clear(&self, buffer_to_clear: ClearBuffer) -> Result<()> {
+        self.port()?.clear(buffer_to_clear)
+    }
+
+    /// Cloning is not supported as the clone could not follow reconnects.
+    fn try_clone(&self) -> Result<Box<dyn SerialPort>> {
+        Err(Error::new(
+            ErrorKind::UNSUPPORTED,
+            "Cloning a reconnecting port is not supported",
+        ))
+    }
+
+    fn set_break(&self) -> Result<()> {
+        self.port()?.set_break()?;
+        self.break_set.set(true);
+        Ok(())
+    }
+
+    fn clear_break(&self) -> Result<()> {
+        self.port()?.clear_break()?;
+        self.break_set.set(false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Modem lines and break state of a [`MockPort`] as applied by the port under test
+    #[derive(Debug, Default)]
+    struct Lines {
+        request_to_send: Option<bool>,
+        data_terminal_ready: Option<bool>,
+        break_set: bool,
+        /// The error all I/O fails with once set
+        failure: Option<ErrorKind>,
+    }
+
+    /// A port recording its modem lines
+    struct MockPort(Arc<Mutex<Lines>>);
+
+    impl MockPort {
+        fn io_result<T>(&self, value: T) -> io::Result<T> {
+            match self.0.lock().unwrap().failure {
+                Some(kind) => Err(io::Error::from(Error::new(kind, "failure"))),
+                None => Ok(value),
+            }
+        }
+    }
+
+    impl io::Read for MockPort {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            self.io_result(0)
+        }
+    }
+
+    impl io::Write for MockPort {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.io_result(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.io_result(())
+        }
+    }
+
+    impl SerialPort for MockPort {
+        fn name(&self) -> Option<String> {
+            None
+        }
+        fn baud_rate(&self) -> Result<u32> {
+            Ok(9600)
+        }
+        fn data_bits(&self) -> Result<DataBits> {
+            Ok(DataBits::Eight)
+        }
+        fn flow_control(&self) -> Result<FlowControl> {
+            Ok(FlowControl::None)
+        }
+        fn parity(&self) -> Result<Parity> {
+            Ok(Parity::None)
+        }
+        fn stop_bits(&self) -> Result<StopBits> {
+            Ok(StopBits::One)
+        }
+        fn timeout(&self) -> Duration {
+            Duration::ZERO
+        }
+        fn set_baud_rate(&mut self, _: u32) -> Result<()> {
+            Ok(())
+        }
+        fn set_data_bits(&mut self, _: DataBits) -> Result<()> {
+            Ok(())
+        }
+        fn set_flow_control(&mut self, _: FlowControl) -> Result<()> {
+            Ok(())
+        }
+        fn set_parity(&mut self, _: Parity) -> Result<()> {
+            Ok(())
+        }
+        fn set_stop_bits(&mut self, _: StopBits) -> Result<()> {
+            Ok(())
+        }
+        fn set_timeout(&mut self, _: Duration) -> Result<()> {
+            Ok(())
+        }
+        fn write_request_to_send(&mut self, level: bool) -> Result<()> {
+            self.0.lock().unwrap().request_to_send = Some(level);
+            Ok(())
+        }
+        fn write_data_terminal_ready(&mut self, level: bool) -> Result<()> {
+            self.0.lock().unwrap().data_terminal_ready = Some(level);
+            Ok(())
+        }
+        fn read_clear_to_send(&mut self) -> Result<bool> {
+            Ok(false)
+        }
+        fn read_data_set_ready(&mut self) -> Result<bool> {
+            Ok(false)
+        }
+        fn read_ring_indicator(&mut self) -> Result<bool> {
+            Ok(false)
+        }
+        fn read_carrier_detect(&mut self) -> Result<bool> {
+            Ok(false)
+        }
+        fn bytes_to_read(&self) -> Result<u32> {
+            Ok(0)
+        }
+        fn bytes_to_write(&self) -> Result<u32> {
+            Ok(0)
+        }
+        fn clear(&self, _: ClearBuffer) -> Result<()> {
+            Ok(())
+        }
+        fn try_clone(&self) -> Result<Box<dyn SerialPort>> {
+            Ok(Box::new(MockPort(self.0.clone())))
+        }
+        fn set_break(&self) -> Result<()> {
+            self.0.lock().unwrap().break_set = true;
+            Ok(())
+        }
+        fn clear_break(&self) -> Result<()> {
+            self.0.lock().unwrap().break_set = false;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reconnect_restores_modem_lines() {
+        let opened = Arc::new(Mutex::new(Vec::<Arc<Mutex<Lines>>>::new()));
+        let open = {
+            let opened = opened.clone();
+            move |_| -> Result<Box<dyn SerialPort>> {
+                let lines = Arc::new(Mutex::new(Lines::default()));
+                opened.lock().unwrap().push(lines.clone());
+                Ok(Box::new(MockPort(lines)))
+            }
+        };
+        let mut port = ReconnectingPort::open_with(crate::new("mock", 9600), Box::new(open))
+            .unwrap()
+            .backoff(Backoff {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(1),
+                max_attempts: Some(1),
+            });
+
+        port.write_request_to_send(true).unwrap();
+        port.write_data_terminal_ready(false).unwrap();
+        port.set_break().unwrap();
+
+        // Ports honoring carrier detect report a device going away as a hang-up.
+        let first = opened.lock().unwrap()[0].clone();
+        first.lock().unwrap().failure = Some(ErrorKind::HANG_UP);
+        assert_eq!(io::Write::write(&mut port, b"hello").unwrap(), 5);
+
+        let opened = opened.lock().unwrap();
+        assert_eq!(opened.len(), 2);
+        let lines = opened[1].lock().unwrap();
+        assert_eq!(lines.request_to_send, Some(true));
+        assert_eq!(lines.data_terminal_ready, Some(false));
+        assert!(lines.break_set);
+    }
+
+    #[test]
+    fn other_errors_are_no_disconnect() {
+        assert!(is_disconnect(&Error::new(ErrorKind::DISCONNECTED, "")));
+        assert!(is_disconnect(&Error::new(ErrorKind::HANG_UP, "")));
+        assert!(is_disconnect(&Error::new(ErrorKind::NoDevice, "")));
+        assert!(!is_disconnect(&Error::new(
+            ErrorKind::Io(io::ErrorKind::TimedOut),
+            ""
+        )));
+        assert!(!is_disconnect(&Error::new(ErrorKind::Unknown, "")));
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use crate::{Error, ErrorKind, Result, SerialPort, SerialPortBuilder};
+
+/// Settings which the driver did not apply as requested
+#[derive(Debug, Default)]
+pub(crate) struct Mismatches(Vec<String>);
+
+impl Mismatches {
+    /// Records `setting` if the `actual` value differs from the `requested` one
+    pub(crate) fn check<T: PartialEq + fmt::Display>(
+        &mut self,
+        setting: &str,
+        requested: T,
+        actual: T,
+    ) {
+        if requested != actual {
+            self.0.push(format!(
+                "{}: requested {}, actual {}",
+                setting, requested, actual
+            ));
+        }
+    }
+
+    /// Fails with `Unsupported` listing all recorded settings, if any
+    pub(crate) fn into_result(self) -> Result<()> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::UNSUPPORTED,
+            format!(
+                "The device did not apply the requested settings ({})",
+                self.0.join("; ")
+            ),
+        ))
+    }
+}
+
+impl SerialPortBuilder {
+    /// Reads back the settings of `port` and compares them to the requested ones if requested
+    /// with [`SerialPortBuilder::strict`].
+    pub(crate) fn verify_settings(&self, port: &dyn SerialPort) -> Result<()> {
+        if !self.strict {
+            return Ok(());
+        }
+        let mut mismatches = Mismatches::default();
+        mismatches.check("baud rate", self.baud_rate, port.baud_rate()?);
+        mismatches.check("data bits", self.data_bits, port.data_bits()?);
+        mismatches.check("parity", self.parity, port.parity()?);
+        mismatches.check("stop bits", self.stop_bits, port.stop_bits()?);
+        mismatches.check("flow control", self.flow_control, port.flow_control()?);
+        mismatches.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parity;
+
+    #[test]
+    fn mismatches_list_every_setting() {
+        assert!(Mismatches::default().into_result().is_ok());
+
+        let mut mismatches = Mismatches::default();
+        mismatches.check("baud rate", 250_000, 230_400);
+        mismatches.check("data bits", 8, 8);
+        mismatches.check("parity", Parity::Even, Parity::None);
+        let error = mismatches.into_result().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UNSUPPORTED);
+        assert_eq!(
+            error.description,
+            "The device did not apply the requested settings (baud rate: requested 250000, \
+             actual 230400; parity: requested Even, actual None)"
+        );
+    }
+}
@@ -2,6 +2,8 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(test)] {
+        #[cfg(unix)]
+        pub(crate) mod fs;
         pub(crate) mod timeout;
     }
 }
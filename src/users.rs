@@ -0,0 +1,222 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::Result;
+#[cfg(not(target_os = "linux"))]
+use crate::{Error, ErrorKind};
+
+/// A process holding a serial port open
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortProcess {
+    /// Process ID
+    pub pid: u32,
+    /// Command name of the process (like `screen` or `ModemManager`)
+    pub command: String,
+}
+
+/// Everything currently using a serial port, as determined by [`port_users`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortUsers {
+    /// Processes with an open handle to the port
+    pub processes: Vec<PortProcess>,
+    /// Whether the port is used as a kernel console
+    pub console: bool,
+}
+
+impl PortUsers {
+    /// Returns `true` if neither a process nor the kernel console is using the port
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty() && !self.console
+    }
+}
+
+impl fmt::Display for PortUsers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut users: Vec<String> = self
+            .processes
+            .iter()
+            .map(|process| format!("{} (pid {})", process.command, process.pid))
+            .collect();
+        if self.console {
+            users.push("the kernel console".to_string());
+        }
+
+        if users.is_empty() {
+            write!(f, "not in use")
+        } else {
+            write!(f, "in use by {}", users.join(", "))
+        }
+    }
+}
+
+/// Determines which processes currently hold the serial port at `path` open
+///
+/// This scans the open file descriptors of all processes in `/proc` and checks whether the port
+/// is a kernel console in `/proc/consoles`. Only the processes whose file descriptors are
+/// accessible are found. These are usually just the processes of the current user unless running
+/// as root.
+///
+/// ```no_run
+/// let users = serialport::port_users("/dev/ttyUSB0").expect("Failed to look up users");
+/// for process in users.processes {
+///     println!("{} (pid {}) has the port open", process.command, process.pid);
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// * `Io` if `path` could not be resolved (like when it does not exist).
+/// * `Unsupported` on platforms other than Linux.
+pub fn port_users<P: AsRef<Path>>(path: P) -> Result<PortUsers> {
+    #[cfg(target_os = "linux")]
+    return linux::port_users(path.as_ref());
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        Err(Error::new(
+            ErrorKind::UNSUPPORTED,
+            "Looking up port users is not supported on this platform",
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs;
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    use std::path::Path;
+
+    use super::{PortProcess, PortUsers};
+    use crate::{Error, Result};
+
+    pub(super) fn port_users(path: &Path) -> Result<PortUsers> {
+        let device = path.canonicalize().map_err(|e| {
+            let description = format!("Failed to resolve {}: {}", path.display(), e);
+            Error::from(e).with_description(description)
+        })?;
+        let rdev = fs::metadata(&device)
+            .ok()
+            .filter(|metadata| metadata.file_type().is_char_device())
+            .map(|metadata| metadata.rdev());
+        Ok(scan_proc(Path::new("/proc"), &device, rdev))
+    }
+
+    /// Looks up the users of `device` in the proc file system at `proc_root`. File descriptors
+    /// match if they link to `device` or to a character device with the device number `rdev`.
+    pub(super) fn scan_proc(proc_root: &Path, device: &Path, rdev: Option<u64>) -> PortUsers {
+        let refers_to_device = |fd: &Path| {
+            fs::read_link(fd).map_or(false, |target| target == device)
+                || rdev.map_or(false, |rdev| {
+                    fs::metadata(fd).map_or(false, |metadata| {
+                        metadata.file_type().is_char_device() && metadata.rdev() == rdev
+                    })
+                })
+        };
+
+        let mut processes = Vec::new();
+        let entries = proc_root.read_dir().into_iter().flatten().flatten();
+        for entry in entries {
+            let pid = match entry.file_name().to_string_lossy().parse() {
+                Ok(pid) => pid,
+                Err(_) => continue,
+            };
+            // File descriptors of processes of other users are not accessible.
+            let fds = match entry.path().join("fd").read_dir() {
+                Ok(fds) => fds,
+                Err(_) => continue,
+            };
+            if fds.flatten().any(|fd| refers_to_device(&fd.path())) {
+                let command = fs::read_to_string(entry.path().join("comm"))
+                    .map(|command| command.trim().to_string())
+                    .unwrap_or_default();
+                processes.push(PortProcess { pid, command });
+            }
+        }
+        processes.sort_by_key(|process| process.pid);
+
+        PortUsers {
+            processes,
+            console: is_console(proc_root, device, rdev),
+        }
+    }
+
+    /// Checks whether `device` is listed in `/proc/consoles`. Its lines start with the name of the
+    /// console device and end with its device number like `ttyS0 -W- (EC p a) 4:64`.
+    fn is_console(proc_root: &Path, device: &Path, rdev: Option<u64>) -> bool {
+        let consoles = match fs::read_to_string(proc_root.join("consoles")) {
+            Ok(consoles) => consoles,
+            Err(_) => return false,
+        };
+        let name = device.file_name().map(|name| name.to_string_lossy());
+
+        consoles.lines().any(|line| {
+            let mut fields = line.split_whitespace();
+            let console_name = fields.next();
+            let console_rdev = fields.last().and_then(|number| {
+                let (major, minor) = number.split_once(':')?;
+                Some(nix::sys::stat::makedev(
+                    major.parse().ok()?,
+                    minor.parse().ok()?,
+                ))
+            });
+            (console_name.is_some() && console_name == name.as_deref())
+                || (rdev.is_some() && console_rdev == rdev)
+        })
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::tests::fs::TempDir;
+
+    #[test]
+    fn scan_proc_finds_processes_and_consoles() {
+        let proc_root = TempDir::new("proc");
+        proc_root.file("1234/comm", "screen\n");
+        proc_root.symlink("1234/fd/0", "/dev/pts/2");
+        proc_root.symlink("1234/fd/5", "/dev/ttyUSB0");
+        proc_root.file("567/comm", "ModemManager\n");
+        proc_root.symlink("567/fd/12", "/dev/ttyUSB0");
+        proc_root.file("89/comm", "bash\n");
+        proc_root.symlink("89/fd/0", "/dev/pts/2");
+        proc_root.file("self/comm", "test\n");
+        proc_root.file(
+            "consoles",
+            "tty0                 -WU (EC p  )    4:1\nttyS0                -W- (E  p a)    4:64\n",
+        );
+
+        let users = linux::scan_proc(proc_root.path(), Path::new("/dev/ttyUSB0"), None);
+        assert_eq!(
+            users,
+            PortUsers {
+                processes: vec![
+                    PortProcess {
+                        pid: 567,
+                        command: "ModemManager".to_string(),
+                    },
+                    PortProcess {
+                        pid: 1234,
+                        command: "screen".to_string(),
+                    },
+                ],
+                console: false,
+            }
+        );
+        assert_eq!(
+            users.to_string(),
+            "in use by ModemManager (pid 567), screen (pid 1234)"
+        );
+
+        let users = linux::scan_proc(proc_root.path(), Path::new("/dev/ttyS0"), None);
+        assert!(users.processes.is_empty());
+        assert!(users.console);
+        assert_eq!(users.to_string(), "in use by the kernel console");
+
+        let users = linux::scan_proc(proc_root.path(), Path::new("/dev/ttyS1"), None);
+        assert!(users.is_empty());
+    }
+}
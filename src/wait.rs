@@ -0,0 +1,172 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::watch::HotplugSignal;
+use crate::{ErrorKind, Result, SerialPort, SerialPortBuilder};
+
+/// Delay before the first retry, doubling up to [`MAX_DELAY`] with each retry
+const INITIAL_DELAY: Duration = Duration::from_millis(10);
+/// Maximum delay between retries
+const MAX_DELAY: Duration = Duration::from_millis(500);
+/// How long to wait for udev to apply the permissions of a new device
+const PERMISSION_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+impl SerialPortBuilder {
+    /// Open the port, waiting for up to `timeout` for it to become available
+    ///
+    /// Right after a USB serial adapter has been plugged in or a device rebooted (like into its
+    /// bootloader), its port briefly does not exist, is busy or denies access until udev has
+    /// applied its permissions. This retries opening the port with an increasing delay on these
+    /// errors. Hotplug notifications cut the delay short where available (see
+    /// [`PortWatcher`](crate::PortWatcher)).
+    ///
+    /// Missing permissions are only retried for a grace period of two seconds after they have
+    /// been reported first. Busy ports are only told apart from other errors (and retried) with
+    /// the feature `error-details`.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// let port = serialport::new("/dev/ttyACM0", 115_200)
+    ///     .open_wait(Duration::from_secs(5))
+    ///     .expect("Failed to open port");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The last error from opening the port if it did not become available within `timeout`.
+    /// Other errors are returned right away. See [`open`](SerialPortBuilder::open) for the
+    /// errors.
+    pub fn open_wait(self, timeout: Duration) -> Result<Box<dyn SerialPort>> {
+        retry(timeout, || self.clone().open())
+    }
+}
+
+/// Calls `open` until it succeeds, fails with an error which is not transient or `timeout` has
+/// passed.
+fn retry<T>(timeout: Duration, mut open: impl FnMut() -> Result<T>) -> Result<T> {
+    let deadline = Instant::now() + timeout;
+    // Set up the notifications before the first attempt to not miss the device appearing in
+    // between.
+    let mut signal = HotplugSignal::new();
+    let mut delay = INITIAL_DELAY;
+    let mut permission_denied_since = None;
+
+    loop {
+        let error = match open() {
+            Ok(port) => return Ok(port),
+            Err(error) => error,
+        };
+
+        let now = Instant::now();
+        let transient = match error.kind() {
+            ErrorKind::NoDevice | ErrorKind::Io(io::ErrorKind::NotFound) => true,
+            #[cfg(feature = "error-details")]
+            ErrorKind::Busy => true,
+            ErrorKind::PERMISSION_DENIED => {
+                let since = *permission_denied_since.get_or_insert(now);
+                now.duration_since(since) < PERMISSION_GRACE_PERIOD
+            }
+            _ => false,
+        };
+        if !transient || now >= deadline {
+            return Err(error);
+        }
+
+        signal.wait(delay.min(deadline - now));
+        delay = (delay * 2).min(MAX_DELAY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn retry_succeeds_after_transient_errors() {
+        let mut attempts = 0;
+        let result = retry(Duration::from_secs(5), || {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::new(
+                    ErrorKind::Io(io::ErrorKind::NotFound),
+                    "missing",
+                )),
+                2 => Err(Error::new(ErrorKind::NoDevice, "no device")),
+                3 => Err(Error::new(ErrorKind::PERMISSION_DENIED, "denied")),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(result.unwrap(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "error-details")]
+    fn retry_retries_busy_devices() {
+        let mut attempts = 0;
+        let result = retry(Duration::from_secs(5), || {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::new(ErrorKind::Busy, "busy")),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn retry_returns_other_errors_right_away() {
+        let mut attempts = 0;
+        let error = retry(Duration::from_secs(5), || -> Result<()> {
+            attempts += 1;
+            Err(Error::new(ErrorKind::InvalidInput, "invalid"))
+        })
+        .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn retry_returns_last_error_at_deadline() {
+        let start = Instant::now();
+        let mut attempts = 0;
+        let error = retry(Duration::from_millis(100), || -> Result<()> {
+            attempts += 1;
+            Err(Error::new(
+                ErrorKind::NoDevice,
+                format!("missing {}", attempts),
+            ))
+        })
+        .unwrap_err();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(error.kind(), ErrorKind::NoDevice);
+        assert_eq!(error.description, format!("missing {}", attempts));
+        assert!(attempts > 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn open_wait_waits_for_port_to_appear() {
+        use crate::tests::fs::TempDir;
+        use crate::TTYPort;
+
+        let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+        let target = slave.name().expect("Slave has no name");
+        let dir = TempDir::new("wait");
+        let link = dir.path().join("ttyACM0");
+
+        let appear = std::thread::spawn({
+            let link = link.clone();
+            move || {
+                std::thread::sleep(Duration::from_millis(100));
+                std::os::unix::fs::symlink(target, link).unwrap();
+            }
+        });
+        let port = crate::new(link.to_str().unwrap(), 0)
+            .open_wait(Duration::from_secs(5))
+            .expect("Port did not become available");
+        assert_eq!(port.name(), Some(link.to_str().unwrap().to_string()));
+        appear.join().unwrap();
+    }
+}
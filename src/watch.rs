@@ -0,0 +1,288 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::{available_ports, Error, ErrorKind, Result, SerialPortInfo};
+
+#[cfg(unix)]
+pub(crate) use crate::posix::HotplugSignal;
+
+/// Polling for changes is the fallback on platforms without hotplug notifications.
+#[cfg(not(unix))]
+pub(crate) enum HotplugSignal {
+    Poll,
+}
+
+#[cfg(not(unix))]
+impl HotplugSignal {
+    pub(crate) fn new() -> Self {
+        HotplugSignal::Poll
+    }
+
+    pub(crate) fn wait(&mut self, timeout: Duration) -> bool {
+        thread::sleep(timeout);
+        true
+    }
+}
+
+/// The default interval for checking for changes when no hotplug notifications are available.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A change of the available serial ports reported by [`PortWatcher`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PortEvent {
+    /// A port appeared
+    Added(SerialPortInfo),
+    /// A port disappeared
+    Removed(SerialPortInfo),
+}
+
+/// Watches for serial ports appearing and disappearing
+///
+/// The watcher compares the result of [`available_ports`] against the ports found when it was
+/// created and reports the differences as [`PortEvent`]s. Changes are detected through udev (with
+/// the `libudev` feature enabled) or inotify on Linux. Other platforms poll for changes.
+///
+/// Events can be received either through the blocking `Iterator` implementation or via
+/// [`recv_timeout`](PortWatcher::recv_timeout) and [`try_recv`](PortWatcher::try_recv). The
+/// background thread watching for changes stops when the watcher gets dropped.
+///
+/// ```no_run
+/// use serialport::{PortEvent, PortWatcher};
+///
+/// let watcher = PortWatcher::new().expect("Failed to watch ports");
+/// for event in watcher {
+///     match event {
+///         PortEvent::Added(info) => println!("Plugged in: {}", info.port_name),
+///         PortEvent::Removed(info) => println!("Unplugged: {}", info.port_name),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PortWatcher {
+    events: mpsc::Receiver<PortEvent>,
+    running: Arc<AtomicBool>,
+}
+
+impl PortWatcher {
+    /// Starts watching for serial ports appearing and disappearing
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the initial list of ports could not be determined or if the background
+    /// thread could not be started.
+    pub fn new() -> Result<Self> {
+        Self::with_poll_interval(DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Starts watching with a custom interval for polling
+    ///
+    /// The interval determines how often to look for changes on platforms without hotplug
+    /// notifications. It also is the maximum time the background thread takes for noticing that
+    /// the watcher got dropped.
+    ///
+    /// ## Errors
+    ///
+    /// See [`new`](PortWatcher::new).
+    pub fn with_poll_interval(interval: Duration) -> Result<Self> {
+        Self::spawn(interval, HotplugSignal::new, available_ports)
+    }
+
+    /// Starts the background thread comparing the result of `enumerate` each time `new_signal`'s
+    /// signal fires.
+    fn spawn<S, E>(interval: Duration, new_signal: S, mut enumerate: E) -> Result<Self>
+    where
+        S: FnOnce() -> HotplugSignal + Send + 'static,
+        E: FnMut() -> Result<Vec<SerialPortInfo>> + Send + 'static,
+    {
+        let (events_tx, events) = mpsc::channel();
+        let (ready_tx, ready) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+
+        thread::Builder::new()
+            .name("serialport-watcher".to_string())
+            .spawn(move || {
+                // Set up the notifications before getting the initial list of ports to not miss
+                // any change in between.
+                let mut signal = new_signal();
+                let mut known = match enumerate() {
+                    Ok(ports) => {
+                        let _ = ready_tx.send(Ok(()));
+                        ports
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                while thread_running.load(Ordering::SeqCst) {
+                    if !signal.wait(interval) {
+                        continue;
+                    }
+                    let ports = match enumerate() {
+                        Ok(ports) => ports,
+                        Err(_) => continue,
+                    };
+                    for event in diff(&known, &ports) {
+                        if events_tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                    known = ports;
+                }
+            })?;
+
+        ready.recv().map_err(|_| {
+            Error::new(
+                ErrorKind::Unknown,
+                "Port watcher thread terminated unexpectedly",
+            )
+        })??;
+
+        Ok(PortWatcher { events, running })
+    }
+
+    /// Waits for the next change for up to `timeout`
+    ///
+    /// Returns `None` if nothing changed in the meantime.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<PortEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// Returns the next change if one is pending, without blocking
+    pub fn try_recv(&self) -> Option<PortEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl Iterator for PortWatcher {
+    type Item = PortEvent;
+
+    /// Blocks until the next change
+    fn next(&mut self) -> Option<PortEvent> {
+        self.events.recv().ok()
+    }
+}
+
+impl Drop for PortWatcher {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Determines the events for getting from the `old` list of ports to the `new` one. A port whose
+/// information changed is reported as removed and added again.
+fn diff(old: &[SerialPortInfo], new: &[SerialPortInfo]) -> Vec<PortEvent> {
+    let removed = old
+        .iter()
+        .filter(|port| !new.contains(port))
+        .map(|port| PortEvent::Removed(port.clone()));
+    let added = new
+        .iter()
+        .filter(|port| !old.contains(port))
+        .map(|port| PortEvent::Added(port.clone()));
+
+    removed.chain(added).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerialPortType;
+    use std::slice;
+
+    fn port(name: &str, port_type: SerialPortType) -> SerialPortInfo {
+        SerialPortInfo {
+            port_name: name.to_string(),
+            port_type,
+            #[cfg(feature = "serialportinfo-details")]
+            aliases: Vec::new(),
+            #[cfg(feature = "serialportinfo-details")]
+            driver: None,
+            #[cfg(feature = "serialportinfo-details")]
+            sysfs_path: None,
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_ports() {
+        let s0 = port("/dev/ttyS0", SerialPortType::PciPort);
+        let usb0 = port("/dev/ttyUSB0", SerialPortType::Unknown);
+        let acm0 = port("/dev/ttyACM0", SerialPortType::Unknown);
+
+        assert!(diff(slice::from_ref(&s0), slice::from_ref(&s0)).is_empty());
+        assert_eq!(
+            diff(&[s0.clone(), usb0.clone()], &[s0.clone(), acm0.clone()]),
+            vec![PortEvent::Removed(usb0), PortEvent::Added(acm0)]
+        );
+    }
+
+    #[test]
+    fn diff_reports_changed_port_as_replugged() {
+        let before = port("/dev/ttyUSB0", SerialPortType::Unknown);
+        let after = port("/dev/ttyUSB0", SerialPortType::BluetoothPort);
+
+        assert_eq!(
+            diff(slice::from_ref(&before), slice::from_ref(&after)),
+            vec![PortEvent::Removed(before), PortEvent::Added(after)]
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "serialportinfo-details"))]
+    fn watcher_reports_links_appearing_after_the_port() {
+        use crate::tests::fs::TempDir;
+        use crate::EnumerationOptions;
+
+        let sys = TempDir::new("sys");
+        let dev = TempDir::new("dev");
+        sys.dir("class/tty");
+        // The directory of the links already exists for other devices.
+        dev.dir("serial/by-path");
+        let options = EnumerationOptions {
+            sysfs_root: sys.path().to_path_buf(),
+            dev_root: dev.path().to_path_buf(),
+            ..EnumerationOptions::default()
+        };
+        let dev_root = dev.path().to_path_buf();
+        let watcher = PortWatcher::spawn(
+            Duration::from_secs(1),
+            move || HotplugSignal::inotify(&dev_root).expect("Unable to watch device root"),
+            move || crate::available_ports_with(&options),
+        )
+        .unwrap();
+
+        // udev creates the persistent links of a port only after its device node.
+        let device = "devices/pci0000:00/0000:00:16.4";
+        let class_dir = sys.dir(&format!("{}/tty/ttyS5", device));
+        sys.symlink(&format!("{}/subsystem", device), sys.dir("bus/pci"));
+        sys.symlink(
+            &format!("{}/tty/ttyS5/device", device),
+            sys.path().join(device),
+        );
+        sys.symlink("class/tty/ttyS5", &class_dir);
+        let node = dev.file("ttyS5", "");
+        let port = match watcher.recv_timeout(Duration::from_secs(5)) {
+            Some(PortEvent::Added(port)) => port,
+            event => panic!("Unexpected event {:?}", event),
+        };
+        assert!(port.aliases.is_empty());
+
+        let link = dev.symlink("serial/by-path/pci-0000:00:16.4", &node);
+        assert_eq!(
+            watcher.recv_timeout(Duration::from_secs(5)),
+            Some(PortEvent::Removed(port.clone()))
+        );
+        assert_eq!(
+            watcher.recv_timeout(Duration::from_secs(5)),
+            Some(PortEvent::Added(SerialPortInfo {
+                aliases: vec![link.to_string_lossy().into_owned()],
+                ..port
+            }))
+        );
+    }
+}
@@ -45,11 +45,25 @@ impl COMPort {
     ///
     /// ## Errors
     ///
-    /// * `NoDevice` if the device could not be opened. This could indicate that
-    ///    the device is already in use.
-    /// * `InvalidInput` if `port` is not a valid device name.
+    /// * `NoDevice` if the device could not be found.
+    /// * `PermissionDenied` (with the feature `error-details`) if access to the
+    ///   device is denied. Windows reports devices which are already in use by
+    ///   another process this way too. Without the feature, this is `NoDevice`.
+    /// * `Busy` (with the feature `error-details`) if the device refuses to be
+    ///   shared.
+    /// * `InvalidInput` if `port` is not a valid device name or the USB
+    ///   adapter does not support the settings (see
+    ///   [`SerialPortBuilder::check_usb_chip`]).
+    /// * `Unsupported` (with the feature `error-details`) if the device did
+    ///   not apply the settings in strict mode (see
+    ///   [`SerialPortBuilder::strict`]).
     /// * `Io` for any other I/O error while opening or initializing the device.
     pub fn open(builder: &SerialPortBuilder) -> Result<COMPort> {
+        builder.verify_baud_rate_tolerance()?;
+        if !builder.keep_existing_settings {
+            builder.verify_usb_chip()?;
+        }
+
         let mut name = Vec::<u16>::with_capacity(4 + builder.path.len() + 1);
 
         if !builder.path.starts_with('\\') {
@@ -79,18 +93,21 @@ impl COMPort {
         // if one of the calls to `get_dcb()` or `set_dcb()` fails
         let mut com = COMPort::open_from_raw_handle(handle as RawHandle);
 
-        let mut dcb = dcb::get_dcb(handle)?;
-        dcb::init(&mut dcb);
-        dcb::set_baud_rate(&mut dcb, builder.baud_rate);
-        dcb::set_data_bits(&mut dcb, builder.data_bits);
-        dcb::set_parity(&mut dcb, builder.parity);
-        dcb::set_stop_bits(&mut dcb, builder.stop_bits);
-        dcb::set_flow_control(&mut dcb, builder.flow_control);
-        dcb::set_dcb(handle, dcb)?;
-
-        // Try to set DTR on best-effort.
-        if let Some(dtr) = builder.dtr_on_open {
-            let _ = com.write_data_terminal_ready(dtr);
+        if !builder.keep_existing_settings {
+            let mut dcb = dcb::get_dcb(handle)?;
+            dcb::init(&mut dcb);
+            dcb::set_baud_rate(&mut dcb, builder.baud_rate);
+            dcb::set_data_bits(&mut dcb, builder.data_bits);
+            dcb::set_parity(&mut dcb, builder.parity);
+            dcb::set_stop_bits(&mut dcb, builder.stop_bits);
+            dcb::set_flow_control(&mut dcb, builder.flow_control);
+            dcb::set_dcb(handle, dcb)?;
+            builder.verify_settings(&com)?;
+
+            // Try to set DTR on best-effort.
+            if let Some(dtr) = builder.dtr_on_open {
+                let _ = com.write_data_terminal_ready(dtr);
+            }
         }
 
         com.set_timeout(builder.timeout)?;
@@ -11,7 +11,9 @@ use winapi::um::setupapi::*;
 use winapi::um::winnt::{KEY_READ, REG_SZ};
 use winapi::um::winreg::*;
 
-use crate::{Error, ErrorKind, Result, SerialPortInfo, SerialPortType, UsbPortInfo};
+use crate::{
+    EnumerationOptions, Error, ErrorKind, Result, SerialPortInfo, SerialPortType, UsbPortInfo,
+};
 
 const CONNECTOR_PUNCTUATION_SELECTION: &[char] = &[':', '_', '\u{ff3f}'];
 
@@ -183,6 +185,16 @@ fn parse_usb_port_info(hardware_id: &str, parent_hardware_id: Option<&str>) -> O
 
         #[cfg(feature = "usbportinfo-interface")]
         interface,
+        #[cfg(feature = "usbportinfo-details")]
+        interface_description: None,
+        #[cfg(feature = "usbportinfo-details")]
+        bcd_device: None,
+        #[cfg(feature = "usbportinfo-details")]
+        bus_number: None,
+        #[cfg(feature = "usbportinfo-details")]
+        device_address: None,
+        #[cfg(feature = "usbportinfo-details")]
+        port_path: None,
     })
 }
 
@@ -518,7 +530,9 @@ fn get_registry_com_ports() -> HashSet<String> {
 }
 
 /// List available serial ports on the system.
-pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
+///
+/// The enumeration options only apply to Linux and FreeBSD and are ignored.
+pub fn available_ports_with(_options: &EnumerationOptions) -> Result<Vec<SerialPortInfo>> {
     let mut ports = Vec::new();
     for guid in get_ports_guids()? {
         let port_devices = PortDevices::new(&guid);
@@ -544,6 +558,12 @@ pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
             ports.push(SerialPortInfo {
                 port_name,
                 port_type: port_device.port_type(),
+                #[cfg(feature = "serialportinfo-details")]
+                aliases: Vec::new(),
+                #[cfg(feature = "serialportinfo-details")]
+                driver: None,
+                #[cfg(feature = "serialportinfo-details")]
+                sysfs_path: None,
             });
         }
     }
@@ -559,6 +579,12 @@ pub fn available_ports() -> Result<Vec<SerialPortInfo>> {
             ports.push(SerialPortInfo {
                 port_name: raw_port,
                 port_type: SerialPortType::Unknown,
+                #[cfg(feature = "serialportinfo-details")]
+                aliases: Vec::new(),
+                #[cfg(feature = "serialportinfo-details")]
+                driver: None,
+                #[cfg(feature = "serialportinfo-details")]
+                sysfs_path: None,
             })
         }
     }
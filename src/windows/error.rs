@@ -14,12 +14,34 @@ use crate::{Error, ErrorKind};
 pub fn last_os_error() -> Error {
     let errno = errno();
 
-    let kind = match errno {
-        ERROR_FILE_NOT_FOUND | ERROR_PATH_NOT_FOUND | ERROR_ACCESS_DENIED => ErrorKind::NoDevice,
+    Error::new(errno_kind(errno), error_string(errno).trim())
+        .with_raw_os_error(errno as i32)
+        .with_source(io::Error::from_raw_os_error(errno as i32))
+}
+
+/// Determines the kind of error for the error code `errno`.
+#[cfg(feature = "error-details")]
+fn errno_kind(errno: u32) -> ErrorKind {
+    match errno {
+        ERROR_FILE_NOT_FOUND | ERROR_PATH_NOT_FOUND => ErrorKind::NoDevice,
+        // Windows reports COM ports which are open elsewhere as access denied, which can't be told
+        // apart from missing permissions.
+        ERROR_ACCESS_DENIED => ErrorKind::PermissionDenied,
+        ERROR_SHARING_VIOLATION => ErrorKind::Busy,
+        ERROR_SEM_TIMEOUT | WAIT_TIMEOUT => ErrorKind::TimedOut,
+        ERROR_NOT_SUPPORTED | ERROR_INVALID_FUNCTION => ErrorKind::Unsupported,
+        ERROR_DEVICE_REMOVED => ErrorKind::Disconnected,
         _ => ErrorKind::Io(io::ErrorKind::Other),
-    };
+    }
+}
 
-    Error::new(kind, error_string(errno).trim())
+/// Determines the kind of error for the error code `errno`.
+#[cfg(not(feature = "error-details"))]
+fn errno_kind(errno: u32) -> ErrorKind {
+    match errno {
+        ERROR_FILE_NOT_FOUND | ERROR_PATH_NOT_FOUND | ERROR_ACCESS_DENIED => ErrorKind::NoDevice,
+        _ => ErrorKind::Io(io::ErrorKind::Other),
+    }
 }
 
 // the rest of this module is borrowed from libstd
@@ -0,0 +1,76 @@
+//! Tests for the enumeration backends.
+#![cfg(all(target_os = "linux", not(target_env = "musl"), feature = "libudev"))]
+
+use serialport::{
+    EnumerationOptions, PortEnumerator, SerialPortInfo, SerialPortType, SysfsEnumerator,
+    UdevEnumerator,
+};
+
+// Clears the USB descriptor strings, which udev reports encoded (like with underscores instead of
+// spaces) and sysfs as they are.
+fn without_usb_strings(mut ports: Vec<SerialPortInfo>) -> Vec<SerialPortInfo> {
+    for port in &mut ports {
+        if let SerialPortType::UsbPort(info) = &mut port.port_type {
+            info.serial_number = None;
+            info.manufacturer = None;
+            info.product = None;
+        }
+    }
+    ports
+}
+
+// Describes the differences between the ports found by two enumerators. Returns an empty list if
+// they found the same ports with the same information.
+fn discrepancies(
+    (name_a, mut a): (&str, Vec<SerialPortInfo>),
+    (name_b, mut b): (&str, Vec<SerialPortInfo>),
+) -> Vec<String> {
+    a.sort_by(|x, y| x.port_name.cmp(&y.port_name));
+    b.sort_by(|x, y| x.port_name.cmp(&y.port_name));
+
+    let mut result = Vec::new();
+    for port in &a {
+        match b.iter().find(|other| other.port_name == port.port_name) {
+            Some(other) if other != port => result.push(format!(
+                "{}:\n    {}: {:?}\n    {}: {:?}",
+                port.port_name, name_a, port, name_b, other
+            )),
+            Some(_) => (),
+            None => result.push(format!("{}: only found by {}", port.port_name, name_a)),
+        }
+    }
+    for port in &b {
+        if !a.iter().any(|other| other.port_name == port.port_name) {
+            result.push(format!("{}: only found by {}", port.port_name, name_b));
+        }
+    }
+    result
+}
+
+#[test]
+#[cfg_attr(not(feature = "hardware-tests"), ignore)]
+fn test_enumerators_agree() {
+    let options = EnumerationOptions {
+        include_phantom_ports: true,
+        ..EnumerationOptions::default()
+    };
+    let udev = without_usb_strings(UdevEnumerator.available_ports(&options).unwrap());
+    let sysfs = without_usb_strings(SysfsEnumerator.available_ports(&options).unwrap());
+
+    let discrepancies = discrepancies(("udev", udev), ("sysfs", sysfs));
+    assert!(
+        discrepancies.is_empty(),
+        "enumerators disagree:\n{}",
+        discrepancies.join("\n")
+    );
+}
+
+#[test]
+fn test_udev_enumerator_rejects_custom_roots() {
+    let options = EnumerationOptions {
+        sysfs_root: "/nonexistent/sys".into(),
+        ..EnumerationOptions::default()
+    };
+    let error = UdevEnumerator.available_ports(&options).unwrap_err();
+    assert_eq!(error.kind(), serialport::ErrorKind::InvalidInput);
+}
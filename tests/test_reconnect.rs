@@ -0,0 +1,128 @@
+//! Tests for the `ReconnectingPort` struct.
+#![cfg(unix)]
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serialport::{Backoff, ErrorKind, ReconnectEvent, ReconnectingPort, SerialPort, TTYPort};
+
+// The kind of errors for a disconnected device, which is only told apart with the feature
+// `error-details`
+#[cfg(feature = "error-details")]
+const DISCONNECTED: ErrorKind = ErrorKind::Disconnected;
+#[cfg(not(feature = "error-details"))]
+const DISCONNECTED: ErrorKind = ErrorKind::Io(std::io::ErrorKind::BrokenPipe);
+
+#[test]
+fn test_reconnecting_port_gives_up_after_backoff() {
+    let (mut master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let mut port =
+        ReconnectingPort::open(serialport::new(&name, 0).timeout(Duration::from_millis(100)))
+            .expect("Unable to open port")
+            .backoff(Backoff {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(2),
+                max_attempts: Some(2),
+            })
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()));
+    assert_eq!(port.name(), Some(name));
+
+    master.write_all(b"hello").unwrap();
+    let mut buffer = [0u8; 5];
+    port.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b"hello");
+    assert!(events.lock().unwrap().is_empty());
+
+    // Keeping the slave open prevents the pseudo terminal from being reused (and reopened).
+    drop(master);
+    let error = serialport::Error::from(port.read(&mut buffer).unwrap_err());
+    assert_ne!(error.kind(), DISCONNECTED);
+    assert!(!port.is_connected());
+    assert_eq!(port.bytes_to_read().unwrap_err().kind(), DISCONNECTED);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 5, "{:?}", events);
+    assert!(matches!(events[0], ReconnectEvent::Disconnected(ref e) if e.kind() == DISCONNECTED));
+    assert!(matches!(
+        events[1],
+        ReconnectEvent::Reconnecting { attempt: 1, delay } if delay == Duration::from_millis(1)
+    ));
+    assert!(matches!(
+        events[2],
+        ReconnectEvent::ReconnectFailed { attempt: 1, .. }
+    ));
+    assert!(matches!(
+        events[3],
+        ReconnectEvent::Reconnecting { attempt: 2, delay } if delay == Duration::from_millis(2)
+    ));
+    assert!(matches!(
+        events[4],
+        ReconnectEvent::ReconnectFailed { attempt: 2, .. }
+    ));
+    drop(slave);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "ios", target_os = "macos"), ignore)]
+fn test_reconnecting_port_restores_settings() {
+    let dir = std::env::temp_dir().join(format!("serialport-reconnect-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let link = dir.join("ttyUSB0");
+    let _ = std::fs::remove_file(&link);
+
+    let (master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    std::os::unix::fs::symlink(slave.name().expect("Slave has no name"), &link).unwrap();
+
+    let name = link.to_str().unwrap().to_string();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let mut port =
+        ReconnectingPort::open(serialport::new(&name, 9600).timeout(Duration::from_millis(100)))
+            .expect("Unable to open port")
+            .backoff(Backoff {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(10),
+                max_attempts: Some(10),
+            })
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()));
+    port.set_baud_rate(19200).unwrap();
+    port.set_stop_bits(serialport::StopBits::Two).unwrap();
+    port.set_timeout(Duration::from_millis(50)).unwrap();
+
+    // The device goes away and reappears at the same path as a different pseudo terminal.
+    drop(master);
+    let (mut master, new_slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    std::fs::remove_file(&link).unwrap();
+    std::os::unix::fs::symlink(new_slave.name().expect("Slave has no name"), &link).unwrap();
+
+    port.write_all(b"hello").unwrap();
+    let mut buffer = [0u8; 5];
+    master.set_timeout(Duration::from_millis(1000)).unwrap();
+    master.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b"hello");
+
+    assert!(port.is_connected());
+    assert_eq!(port.name(), Some(name.clone()));
+    assert_eq!(port.baud_rate().unwrap(), 19200);
+    assert_eq!(port.stop_bits().unwrap(), serialport::StopBits::Two);
+    assert_eq!(port.timeout(), Duration::from_millis(50));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 3, "{:?}", events);
+    assert!(matches!(events[0], ReconnectEvent::Disconnected(ref e) if e.kind() == DISCONNECTED));
+    assert!(matches!(
+        events[1],
+        ReconnectEvent::Reconnecting { attempt: 1, .. }
+    ));
+    assert!(
+        matches!(events[2], ReconnectEvent::Reconnected { ref port_name } if *port_name == name)
+    );
+
+    drop(slave);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -8,7 +8,21 @@ use std::os::unix::prelude::*;
 use std::str;
 use std::time::Duration;
 
-use serialport::{SerialPort, TTYPort};
+use serialport::{ErrorKind, SerialPort, TTYPort};
+
+// Kinds of errors which are only told apart with the feature `error-details`
+#[cfg(feature = "error-details")]
+const DISCONNECTED: ErrorKind = ErrorKind::Disconnected;
+#[cfg(not(feature = "error-details"))]
+const DISCONNECTED: ErrorKind = ErrorKind::Io(std::io::ErrorKind::BrokenPipe);
+#[cfg(feature = "error-details")]
+const HANG_UP: ErrorKind = ErrorKind::HangUp;
+#[cfg(not(feature = "error-details"))]
+const HANG_UP: ErrorKind = ErrorKind::Io(std::io::ErrorKind::BrokenPipe);
+#[cfg(feature = "error-details")]
+const UNSUPPORTED: ErrorKind = ErrorKind::Unsupported;
+#[cfg(not(feature = "error-details"))]
+const UNSUPPORTED: ErrorKind = ErrorKind::Io(std::io::ErrorKind::Unsupported);
 
 #[test]
 fn test_ttyport_pair() {
@@ -158,3 +172,320 @@ fn test_ttyport_set_nonstandard_baud() {
     slave.set_baud_rate(1_200_000).unwrap();
     assert_eq!(slave.baud_rate().unwrap(), 1_200_000);
 }
+
+#[test]
+fn test_ttyport_set_hangup_on_close() {
+    // `master` must be used here as Dropping it causes slave to be deleted by the OS.
+    // TODO: Convert this to a statement-level attribute once
+    //       https://github.com/rust-lang/rust/issues/15701 is on stable.
+    // FIXME: Create a mutex across all tests for using `TTYPort::pair()` as it's not threadsafe
+    #![allow(unused_variables)]
+    let (master, mut slave) = TTYPort::pair().expect("Unable to create ptty pair");
+
+    slave.set_hangup_on_close(false).unwrap();
+    assert!(!slave.hangup_on_close().unwrap());
+    slave.set_hangup_on_close(true).unwrap();
+    assert!(slave.hangup_on_close().unwrap());
+}
+
+#[test]
+fn test_ttyport_disconnected() {
+    let (master, mut slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    slave.set_timeout(Duration::from_millis(100)).unwrap();
+    assert!(slave.is_connected().unwrap());
+
+    drop(master);
+    assert!(!slave.is_connected().unwrap());
+
+    let mut buffer = [0u8; 8];
+    let error = serialport::Error::from(slave.read(&mut buffer).unwrap_err());
+    assert_eq!(error.kind(), DISCONNECTED);
+    let error = serialport::Error::from(slave.write(b"hello").unwrap_err());
+    assert_eq!(error.kind(), DISCONNECTED);
+}
+
+#[test]
+fn test_ttyport_canonical_mode_end_of_file() {
+    let (mut master, mut slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    slave.set_timeout(Duration::from_millis(100)).unwrap();
+
+    // Switch the slave to canonical mode as a port adopted with its existing settings might be.
+    let mut termios = std::mem::MaybeUninit::uninit();
+    assert_eq!(
+        unsafe { libc::tcgetattr(slave.as_raw_fd(), termios.as_mut_ptr()) },
+        0
+    );
+    let mut termios = unsafe { termios.assume_init() };
+    termios.c_lflag |= libc::ICANON;
+    assert_eq!(
+        unsafe { libc::tcsetattr(slave.as_raw_fd(), libc::TCSANOW, &termios) },
+        0
+    );
+
+    // An end of file character is a zero-length read and not a hang-up.
+    let mut buffer = [0u8; 8];
+    master.write_all(&[termios.c_cc[libc::VEOF]]).unwrap();
+    assert_eq!(slave.read(&mut buffer).unwrap(), 0);
+    master.write_all(b"line\n").unwrap();
+    assert_eq!(slave.read(&mut buffer).unwrap(), 5);
+    assert_eq!(&buffer[..5], b"line\n");
+
+    drop(master);
+    let error = serialport::Error::from(slave.read(&mut buffer).unwrap_err());
+    assert_eq!(error.kind(), DISCONNECTED);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ttyport_serial_info_unsupported() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+
+    // Pseudo terminals have no UART.
+    let error = slave.serial_info().unwrap_err();
+    #[cfg(feature = "error-details")]
+    {
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+        assert_eq!(error.raw_os_error(), Some(libc::ENOTTY));
+    }
+    #[cfg(not(feature = "error-details"))]
+    assert_eq!(error.kind(), ErrorKind::Unknown);
+    assert!(error.description.starts_with("Failed to read UART details"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_port_info_pseudo_terminal() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let info = serialport::port_info(&name).expect("Unable to look up port info");
+    assert_eq!(info.port_name, name);
+    #[cfg(feature = "serialporttype-details")]
+    assert_eq!(info.port_type, serialport::SerialPortType::PseudoTerminal);
+    #[cfg(not(feature = "serialporttype-details"))]
+    assert_eq!(info.port_type, serialport::SerialPortType::Unknown);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_port_users_lists_own_process() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let users = serialport::port_users(&name).expect("Unable to look up port users");
+    assert!(users
+        .processes
+        .iter()
+        .any(|process| process.pid == std::process::id()));
+    assert!(!users.console);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ttyport_strict() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    // The Linux pseudo terminal driver silently resets parity and data bits.
+    let builder = serialport::new(&name, 9600)
+        .stop_bits(serialport::StopBits::Two)
+        .parity(serialport::Parity::Even)
+        .strict(true);
+    assert!(TTYPort::open(&builder.clone().strict(false)).is_ok());
+    let error = TTYPort::open(&builder).unwrap_err();
+    assert_eq!(error.kind(), UNSUPPORTED);
+    assert!(
+        error
+            .description
+            .ends_with("(parity: requested Even, actual None)"),
+        "{}",
+        error
+    );
+
+    let mut port = TTYPort::open(&builder.parity(serialport::Parity::None))
+        .expect("Unable to open port in strict mode");
+    assert!(port.strict());
+    port.set_baud_rate(19200).unwrap();
+    let error = port.set_data_bits(serialport::DataBits::Seven).unwrap_err();
+    assert_eq!(error.kind(), UNSUPPORTED);
+
+    port.set_strict(false);
+    port.set_data_bits(serialport::DataBits::Seven).unwrap();
+    assert_eq!(port.data_bits().unwrap(), serialport::DataBits::Eight);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ttyport_from_file() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&name)
+        .expect("Unable to open slave");
+    let port = TTYPort::from_file(file).expect("Unable to adopt file");
+    assert_eq!(port.name(), Some(name));
+    assert_eq!(port.timeout(), Duration::from_millis(100));
+    assert!(!port.exclusive());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ttyport_from_file_keeps_exclusive_access() {
+    fn is_exclusive(fd: RawFd) -> bool {
+        let mut exclusive: libc::c_int = 0;
+        assert_eq!(
+            unsafe { libc::ioctl(fd, libc::TIOCGEXCL, &mut exclusive) },
+            0
+        );
+        exclusive != 0
+    }
+
+    let (_master, mut slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    slave.set_exclusive(true).unwrap();
+    assert!(is_exclusive(slave.as_raw_fd()));
+
+    // Dropping a port adopted without claiming exclusive access must not release it.
+    let file = unsafe { std::fs::File::from_raw_fd(libc::dup(slave.as_raw_fd())) };
+    let port = TTYPort::from_file(file).expect("Unable to adopt file");
+    assert!(!port.exclusive());
+    drop(port);
+    assert!(is_exclusive(slave.as_raw_fd()));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ttyport_from_fd_with() {
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let fd = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&name)
+        .expect("Unable to open slave")
+        .into_raw_fd();
+    let builder = serialport::new("", 19200).timeout(Duration::from_millis(10));
+    let options = serialport::AdoptOptions {
+        exclusive: false,
+        ..Default::default()
+    };
+    let port = unsafe { TTYPort::from_fd_with(fd, &builder, options) }.expect("Unable to adopt fd");
+    assert_eq!(port.name(), Some(name));
+    assert_eq!(port.baud_rate().unwrap(), 19200);
+    assert_eq!(port.timeout(), Duration::from_millis(10));
+    assert!(!port.exclusive());
+
+    let options = serialport::AdoptOptions {
+        resolve_name: false,
+        ..Default::default()
+    };
+    let fd = port.into_raw_fd();
+    let port = unsafe { TTYPort::from_fd_with(fd, &builder, options) }.expect("Unable to adopt fd");
+    assert_eq!(port.name(), None);
+    assert!(port.exclusive());
+}
+
+#[rustversion::since(1.63)]
+#[test]
+fn test_ttyport_owned_fd() {
+    use std::os::unix::io::{AsFd, OwnedFd};
+
+    let (_master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let fd = slave.as_raw_fd();
+    assert_eq!(slave.as_fd().as_raw_fd(), fd);
+
+    let owned = OwnedFd::from(slave);
+    assert_eq!(owned.as_raw_fd(), fd);
+    let port = TTYPort::from(owned);
+    assert_eq!(port.as_raw_fd(), fd);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "ios", target_os = "macos"), ignore)]
+fn test_ttyport_keep_existing_settings() {
+    let (_master, mut slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+    slave.set_baud_rate(19200).unwrap();
+    slave.set_stop_bits(serialport::StopBits::Two).unwrap();
+
+    let port = TTYPort::open(
+        &serialport::new(&name, 9600)
+            .stop_bits(serialport::StopBits::One)
+            .timeout(Duration::from_millis(10))
+            .exclusive(false)
+            .keep_existing_settings(),
+    )
+    .expect("Unable to open port");
+    assert_eq!(port.baud_rate().unwrap(), 19200);
+    assert_eq!(port.stop_bits().unwrap(), serialport::StopBits::Two);
+    assert_eq!(port.timeout(), Duration::from_millis(10));
+    assert!(!port.exclusive());
+}
+
+#[test]
+fn test_ttyport_carrier_detect_hang_up() {
+    let (master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let name = slave.name().expect("Slave has no name");
+
+    let mut port = TTYPort::open(
+        &serialport::new(&name, 0)
+            .carrier_detect(true)
+            .timeout(Duration::from_millis(100)),
+    )
+    .expect("Unable to open port");
+    assert!(port.carrier_detect());
+
+    drop(master);
+    let mut buffer = [0u8; 8];
+    let error = serialport::Error::from(port.read(&mut buffer).unwrap_err());
+    assert_eq!(error.kind(), HANG_UP);
+    let error = serialport::Error::from(port.write(b"hello").unwrap_err());
+    assert_eq!(error.kind(), HANG_UP);
+    drop(slave);
+}
+
+#[test]
+fn test_ttyport_shared_reference() {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<TTYPort>();
+
+    let (master, slave) = TTYPort::pair().expect("Unable to create ptty pair");
+    let master = std::sync::Arc::new(master);
+    let slave = std::sync::Arc::new(slave);
+    slave.set_timeout(Duration::from_millis(1000)).unwrap();
+
+    let reader = std::thread::spawn({
+        let slave = slave.clone();
+        move || {
+            let mut buffer = [0u8; 5];
+            (&*slave).read_exact(&mut buffer).unwrap();
+            buffer
+        }
+    });
+    (&*master).write_all(b"hello").unwrap();
+    assert_eq!(&reader.join().unwrap(), b"hello");
+
+    // The timeout and the modem control lines are accessible through shared references from other
+    // threads as well.
+    let carrier_detect = std::thread::spawn({
+        let slave = slave.clone();
+        move || {
+            slave.set_timeout(Duration::from_millis(500)).unwrap();
+            slave.read_carrier_detect().map_err(|e| e.kind())
+        }
+    })
+    .join()
+    .unwrap();
+    assert_eq!(slave.timeout(), Duration::from_millis(500));
+
+    // Pseudo terminals don't necessarily support modem control lines, so compare with the trait
+    // method instead of expecting a particular state.
+    let mut clone = slave.try_clone_native().unwrap();
+    assert_eq!(
+        carrier_detect,
+        SerialPort::read_carrier_detect(&mut clone).map_err(|e| e.kind())
+    );
+}